@@ -51,12 +51,15 @@ pub fn dioxus_elements(input: TokenStream) -> TokenStream {
         quote! { #module }
     });
 
-    // Handle event imports if present
+    // Handle event imports if present. Each module re-exports its events by name, so they take
+    // precedence over the built-in events glob imported alongside them.
     let event_imports = if let Some(events) = events {
         events
             .iter()
             .map(|module| {
-                quote! { pub use super::#module::*; }
+                let last = &module.path.segments.last().unwrap().ident;
+                let macro_ident = format_ident!("all_{}_events", last);
+                quote! { super::#module::macros::#macro_ident! { super::#module } }
             })
             .collect::<Vec<_>>()
     } else {
@@ -126,6 +129,7 @@ pub fn dioxus_elements(input: TokenStream) -> TokenStream {
             }
 
             pub mod events {
+                pub use dioxus_in_bevy::events::*;
                 #(#event_imports)*
             }
         }
@@ -194,6 +198,52 @@ pub fn create_all_elements(input: TokenStream) -> TokenStream {
     output.into()
 }
 
+struct EventList {
+    module_name: Ident,
+    events: Punctuated<Ident, Token![,]>,
+}
+
+impl Parse for EventList {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let module_name = input.parse::<syn::Ident>()?;
+        let events = Punctuated::<Ident, Token![,]>::parse_terminated(input)?;
+
+        Ok(EventList {
+            module_name,
+            events,
+        })
+    }
+}
+
+/// Declares the macro through which [`dioxus_elements!`] re-exports the events of a module
+/// declared with `events!` by name.
+#[proc_macro]
+pub fn create_all_events(input: TokenStream) -> TokenStream {
+    let EventList {
+        module_name,
+        events,
+    } = parse_macro_input!(input as EventList);
+
+    let events = events.iter();
+    let macro_ident = format_ident!("all_{}_events", module_name);
+
+    let output = quote! {
+        pub mod macros {
+            #[macro_export]
+            #[doc(hidden)]
+            macro_rules! #macro_ident {
+                ($($module:tt)*) => {
+                    pub use $($module)*::{#(#events),*};
+                };
+            }
+
+            pub use #macro_ident;
+        }
+    };
+
+    output.into()
+}
+
 #[proc_macro_attribute]
 pub fn bevy_component(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // Parse the incoming item as a function. We assume the body is valid Rust (e.g. `rsx!{ ... }`).
@@ -0,0 +1,97 @@
+//! The state of assets loaded with [`use_asset`](crate::hooks::use_asset), for config files,
+//! localization tables, icons, or any other asset a component reads.
+//!
+//! The state follows the `AssetServer`: it is `Loading` until the asset and its dependencies are
+//! loaded, and set again whenever the asset is hot reloaded, so components reading it render
+//! again with the new version.
+
+use std::sync::Arc;
+
+use bevy::asset::{Asset, AssetLoadError, AssetServer, Handle, RecursiveDependencyLoadState};
+use dioxus::prelude::*;
+use dioxus_core::CapturedError;
+
+/// Whether an asset is loaded, with its handle.
+#[derive(Debug, Clone)]
+pub enum AssetState<H> {
+    /// The asset or one of its dependencies is still loading. The handle already points to it,
+    /// so it can be handed to an element that shows the asset once it's there.
+    Loading(H),
+    Loaded(H),
+    /// The asset or one of its dependencies couldn't be loaded.
+    Failed(Arc<AssetLoadError>),
+}
+
+impl<H> AssetState<H> {
+    /// The handle, while the asset is loading and once it's loaded.
+    pub fn handle(&self) -> Option<&H> {
+        match self {
+            Self::Loading(handle) | Self::Loaded(handle) => Some(handle),
+            Self::Failed(_) => None,
+        }
+    }
+
+    /// The handle, once the asset is loaded.
+    pub fn loaded(&self) -> Option<&H> {
+        match self {
+            Self::Loaded(handle) => Some(handle),
+            _ => None,
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading(_))
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        matches!(self, Self::Loaded(_))
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+}
+
+impl<A: Asset> AssetState<Handle<A>> {
+    /// The state of `handle` in the `AssetServer`.
+    pub(crate) fn of(asset_server: &AssetServer, handle: Handle<A>) -> Self {
+        match asset_server.recursive_dependency_load_state(&handle) {
+            RecursiveDependencyLoadState::Loaded => Self::Loaded(handle),
+            RecursiveDependencyLoadState::Failed(error) => Self::Failed(error),
+            _ => Self::Loading(handle),
+        }
+    }
+}
+
+/// Suspends a component on an asset loaded with [`use_asset`](crate::hooks::use_asset).
+pub trait SuspendAsset<A: Asset> {
+    /// The handle of the loaded asset. Until it is loaded, suspends the component so the closest
+    /// `SuspenseBoundary` renders its fallback, and if it fails to load, returns the error for the
+    /// closest `ErrorBoundary`.
+    ///
+    /// ```rust,ignore
+    /// let portrait = use_asset::<Image>("portraits/hero.png").suspend()?;
+    /// ```
+    fn suspend(&self) -> Result<Handle<A>, RenderError>;
+}
+
+impl<A: Asset> SuspendAsset<A> for Signal<AssetState<Handle<A>>> {
+    fn suspend(&self) -> Result<Handle<A>, RenderError> {
+        match &*self.read() {
+            AssetState::Loaded(handle) => Ok(handle.clone()),
+            AssetState::Failed(error) => Err(RenderError::Aborted(CapturedError::from_display(
+                error.clone(),
+            ))),
+            AssetState::Loading(handle) => {
+                // The boundary shows its fallback until this task is done. The component renders
+                // again once the state it just read changes.
+                let asset_server = consume_context::<AssetServer>();
+                let handle = handle.clone();
+                let task = spawn(async move {
+                    _ = asset_server.wait_for_asset(&handle).await;
+                });
+                Err(RenderError::Suspended(SuspendedFuture::new(task)))
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//! Attributes that are available on every element declared with [`elements!`](crate::elements),
+//! and the [`sets`] of attributes available on the elements declared with them.
+//!
+//! Each element module re-exports these, so `rsx!` accepts them the same way it accepts the
+//! attributes declared on the element itself. An element may still declare its own attribute with
+//! the same name, which takes precedence.
+
+#![allow(non_upper_case_globals)]
+
+macro_rules! global_attributes {
+    ($($name:ident => $mutator:path),* $(,)?) => {
+        $(
+            pub const $name: (&'static str, Option<&'static str>, bool) =
+                (stringify!($name), None, false);
+
+            inventory::submit! {
+                crate::component::GlobalAttribute {
+                    name: stringify!($name),
+                    mutator: $mutator,
+                }
+            }
+        )*
+    };
+}
+
+global_attributes! {
+    tabindex => crate::navigation::set_tab_index,
+    navgroup => crate::navigation::set_nav_group,
+    draggable => crate::drag::set_draggable,
+    disabled => crate::interaction::set_disabled,
+    style => crate::style::set_style,
+    class => crate::stylesheet::set_class,
+    hover_background_color => crate::interaction::set_hover_color::<bevy::ui::BackgroundColor>,
+    pressed_background_color => crate::interaction::set_pressed_color::<bevy::ui::BackgroundColor>,
+    disabled_background_color => crate::interaction::set_disabled_color::<bevy::ui::BackgroundColor>,
+    hover_border_color => crate::interaction::set_hover_color::<bevy::ui::BorderColor>,
+    pressed_border_color => crate::interaction::set_pressed_color::<bevy::ui::BorderColor>,
+    disabled_border_color => crate::interaction::set_disabled_color::<bevy::ui::BorderColor>,
+}
+
+macro_rules! attribute_set {
+    ($($name:ident => $mutator:path),* $(,)?) => {
+        $(
+            pub const $name: (&'static str, Option<&'static str>, bool) =
+                (stringify!($name), None, false);
+        )*
+
+        #[doc(hidden)]
+        pub const MUTATORS: &[(&str, crate::component::AttributeMutator)] =
+            &[$((stringify!($name), $mutator)),*];
+    };
+}
+
+/// Attributes only some elements accept, declared `with` the name of their set in
+/// [`elements!`](crate::elements), like `span with text, TextSpan::default(), { ... }`.
+pub mod sets {
+    /// The attributes of text elements, which have a `Text` or a `TextSpan`. See [`crate::text`].
+    pub mod text {
+        attribute_set! {
+            color => crate::text::set_color,
+            font_size => crate::text::set_font_size,
+            line_height => crate::text::set_line_height,
+            font => crate::text::set_font,
+            weight => crate::text::set_weight,
+            justify => crate::text::set_justify,
+            linebreak => crate::text::set_linebreak,
+        }
+    }
+}
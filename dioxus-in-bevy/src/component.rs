@@ -1,18 +1,33 @@
-use std::rc::Rc;
+use std::{any::TypeId, collections::HashMap, rc::Rc};
 
 use bevy::ecs::entity::Entity;
+use bevy::prelude::{Component, EntityRef, EntityWorldMut, Mut, Resource, World};
 use dioxus_core::{AnyValue, AttributeValue};
 
+use crate::error::{DioxusError, DioxusErrorKind};
+use crate::native::{AttributeValues, DioxusBuilders, DioxusElement, ElementTag};
+use crate::value::FromAttributeValue;
+
+/// Applies an attribute value to the entity of an element.
+pub type AttributeMutator =
+    fn(&mut bevy::prelude::World, Entity, AttributeValue) -> Result<(), DioxusErrorKind>;
+
+/// Reverts an attribute of an element once it was removed, declared with `@remove` in
+/// [`elements!`](crate::elements).
+pub type AttributeRemover = fn(&mut bevy::prelude::World, Entity) -> Result<(), DioxusErrorKind>;
+
+/// Tells whether the setter of an attribute writes the component with the given [`TypeId`].
+pub type AttributeWrites = fn(TypeId) -> bool;
+
 #[derive(Debug, Clone)]
 #[doc(hidden)]
 pub struct ComponentBuilder {
     pub name: &'static str,
     pub builder: fn(&mut bevy::prelude::World) -> Entity,
-    pub initializer: fn(&mut bevy::prelude::World, Entity),
-    pub attribute_mutators: &'static [(
-        &'static str,
-        fn(&mut bevy::prelude::World, Entity, dioxus_core::AttributeValue),
-    )],
+    pub initializer: fn(&mut bevy::prelude::World, Entity) -> Result<(), DioxusErrorKind>,
+    pub attribute_mutators: &'static [(&'static str, AttributeMutator, AttributeWrites)],
+    /// The [sets](crate::attributes::sets) of attributes the element was declared with.
+    pub attribute_sets: &'static [&'static [(&'static str, AttributeMutator)]],
 }
 
 inventory::collect!(ComponentBuilder);
@@ -21,46 +36,215 @@ inventory::collect!(ComponentBuilder);
 #[doc(hidden)]
 pub struct EventHandlerInstaller {
     pub name: &'static str,
+    /// The path of the module declared by [`events!`](crate::events), reported on conflicts.
+    pub module: &'static str,
     pub handler: fn(&mut bevy::prelude::World, Entity, Entity, dioxus_core::ElementId),
 }
 
 inventory::collect!(EventHandlerInstaller);
 
-pub fn attr<T: AnyValue + Clone + 'static>(value: T) -> dioxus_core::AttributeValue {
-    AttributeValue::Any(Rc::new(value))
+/// An attribute that can be set on any element, regardless of the module that declared it.
+#[derive(Debug, Clone)]
+#[doc(hidden)]
+pub struct GlobalAttribute {
+    pub name: &'static str,
+    pub mutator: AttributeMutator,
 }
 
+inventory::collect!(GlobalAttribute);
+
+/// Resets the component an attribute setter queries, when the attribute is removed, to its value
+/// on `defaults`, a copy of the element's bundle from its [`ElementDefaults`]. Components the bundle doesn't have are removed, and components the setter
+/// only reads are left alone.
+///
+/// This replaces the whole component, so [`reapply_attributes`] then sets the other attributes
+/// writing it again. Setters of a single field should rather declare `@remove`.
 #[doc(hidden)]
-pub fn convert_attribute<T: AnyValue + Clone + 'static>(value: dioxus_core::AttributeValue) -> T {
-    let type_name = std::any::type_name::<T>();
+pub trait ResetFrom {
+    /// The component the setter writes, if any.
+    fn written() -> Option<TypeId>;
 
-    if type_name == std::any::type_name::<String>() {
-        match value {
-            AttributeValue::Text(text) => text.as_any().downcast_ref::<T>().unwrap().clone(),
-            _ => panic!("Attribute is not a String!"),
-        }
-    } else if type_name == std::any::type_name::<f64>() {
-        match value {
-            AttributeValue::Float(number) => number.as_any().downcast_ref::<T>().unwrap().clone(),
-            _ => panic!("Attribute is not a number!"),
-        }
-    } else if type_name == std::any::type_name::<i64>() {
-        match value {
-            AttributeValue::Int(number) => number.as_any().downcast_ref::<T>().unwrap().clone(),
-            _ => panic!("Attribute is not a number!"),
+    fn reset_from(defaults: &mut EntityWorldMut, entity: &mut EntityWorldMut);
+}
+
+impl<T: Component> ResetFrom for &mut T {
+    fn written() -> Option<TypeId> {
+        Some(TypeId::of::<T>())
+    }
+
+    fn reset_from(defaults: &mut EntityWorldMut, entity: &mut EntityWorldMut) {
+        match defaults.take::<T>() {
+            Some(component) => entity.insert(component),
+            None => entity.remove::<T>(),
+        };
+    }
+}
+
+impl<T: Component> ResetFrom for Option<&mut T> {
+    fn written() -> Option<TypeId> {
+        <&mut T>::written()
+    }
+
+    fn reset_from(defaults: &mut EntityWorldMut, entity: &mut EntityWorldMut) {
+        <&mut T>::reset_from(defaults, entity);
+    }
+}
+
+impl<T: Component> ResetFrom for &T {
+    fn written() -> Option<TypeId> {
+        None
+    }
+
+    fn reset_from(_: &mut EntityWorldMut, _: &mut EntityWorldMut) {}
+}
+
+impl<T: Component> ResetFrom for Option<&T> {
+    fn written() -> Option<TypeId> {
+        None
+    }
+
+    fn reset_from(_: &mut EntityWorldMut, _: &mut EntityWorldMut) {}
+}
+
+impl ResetFrom for Entity {
+    fn written() -> Option<TypeId> {
+        None
+    }
+
+    fn reset_from(_: &mut EntityWorldMut, _: &mut EntityWorldMut) {}
+}
+
+/// The bundle of each element type, spawned the first time one of its attributes is reset, in a
+/// scratch world so its hooks and observers don't run in the app.
+#[derive(Resource, Default)]
+pub struct ElementDefaults {
+    world: World,
+    bundles: HashMap<&'static str, Entity>,
+}
+
+impl ElementDefaults {
+    fn bundle(&mut self, builder: &ComponentBuilder) -> Entity {
+        *self
+            .bundles
+            .entry(builder.name)
+            .or_insert_with(|| (builder.builder)(&mut self.world))
+    }
+
+    /// The bundle of the element `builder` spawns.
+    pub(crate) fn get(&mut self, builder: &ComponentBuilder) -> EntityRef<'_> {
+        let bundle = self.bundle(builder);
+        self.world.entity(bundle)
+    }
+
+    /// Spawns a copy of the bundle of the element `builder` spawns, to take components from.
+    fn copy(&mut self, builder: &ComponentBuilder) -> Entity {
+        let bundle = self.bundle(builder);
+        let copy = self.world.entity_mut(bundle).clone_and_spawn();
+
+        // Only components that are `Clone` or `Reflect` are copied, so bundles with others are
+        // spawned again
+        let count = |entity: Entity| self.world.entity(entity).archetype().component_count();
+        if count(copy) == count(bundle) {
+            copy
+        } else {
+            self.world.despawn(copy);
+            (builder.builder)(&mut self.world)
         }
-    } else if type_name == std::any::type_name::<bool>() {
-        match value {
-            AttributeValue::Bool(bool) => bool.as_any().downcast_ref::<T>().unwrap().clone(),
-            _ => panic!("Attribute is not a bool!"),
+    }
+}
+
+/// Calls `reset` with a copy of the bundle of the element `tag`, and the entity of one of its
+/// elements, to reset the components an attribute writes once it is removed.
+#[doc(hidden)]
+pub fn reset_to_defaults(
+    world: &mut World,
+    entity: Entity,
+    tag: &'static str,
+    reset: impl FnOnce(&mut EntityWorldMut, &mut EntityWorldMut),
+) {
+    let Some(builder) = world.resource::<DioxusBuilders>().get(tag).copied() else {
+        return;
+    };
+
+    world.resource_scope(|world, mut defaults: Mut<ElementDefaults>| {
+        let copy = defaults.copy(builder);
+        let mut copy = defaults.world.entity_mut(copy);
+        reset(&mut copy, &mut world.entity_mut(entity));
+        copy.despawn();
+    });
+}
+
+/// Sets the attributes of an element that write one of `components` again, with their last
+/// values, after removing the attribute `removed` reset those components to their defaults.
+///
+/// Their cached values are cleared first, so [`AttributeValues`] doesn't skip them. Errors are
+/// reported against the attribute that failed, rather than the one being removed.
+#[doc(hidden)]
+pub fn reapply_attributes(
+    world: &mut World,
+    entity: Entity,
+    removed: &str,
+    components: &[Option<TypeId>],
+) -> Result<(), DioxusErrorKind> {
+    let Some(tag) = world.get::<ElementTag>(entity).map(|tag| tag.0) else {
+        return Ok(());
+    };
+    let Some(builder) = world.resource::<DioxusBuilders>().get(tag).copied() else {
+        return Ok(());
+    };
+
+    let attributes = builder
+        .attribute_mutators
+        .iter()
+        .filter(|(name, _, writes)| {
+            *name != removed
+                && components
+                    .iter()
+                    .flatten()
+                    .any(|&component| writes(component))
+        });
+
+    for &(name, mutator, _) in attributes {
+        let Some(value) = world
+            .non_send_resource_mut::<AttributeValues>()
+            .values
+            .get_mut(&entity)
+            .and_then(|values| values.remove(name))
+        else {
+            continue;
+        };
+
+        let result = crate::theme::resolve_token(world, value.clone())
+            .and_then(|resolved| mutator(world, entity, resolved));
+
+        // Tokens the theme is missing are still kept, as in `set_attribute`
+        if matches!(result, Ok(()) | Err(DioxusErrorKind::UnknownToken(_))) {
+            world
+                .non_send_resource_mut::<AttributeValues>()
+                .values
+                .entry(entity)
+                .or_default()
+                .insert(name, value);
         }
-    } else {
-        match value {
-            AttributeValue::Any(any) => match any.as_any().downcast_ref::<T>() {
-                Some(value) => value.clone(),
-                None => panic!("Attribute is not a {:?}!", std::any::type_name::<T>()),
-            },
-            _ => panic!("Attribute is not a {:?}!", std::any::type_name::<T>()),
+        if let (Err(kind), Some(element)) = (result, world.get::<DioxusElement>(entity).copied()) {
+            let error = DioxusError::new(element.root, kind)
+                .with_element(element.id)
+                .with_tag(Some(tag))
+                .with_attribute(name);
+            crate::error::report(world, error);
         }
     }
+
+    Ok(())
+}
+
+pub fn attr<T: AnyValue + Clone + 'static>(value: T) -> dioxus_core::AttributeValue {
+    AttributeValue::Any(Rc::new(value))
+}
+
+#[doc(hidden)]
+pub fn convert_attribute<T: FromAttributeValue>(
+    value: dioxus_core::AttributeValue,
+) -> Result<T, DioxusErrorKind> {
+    T::from_attribute_value(value)
 }
@@ -0,0 +1,271 @@
+//! Drag gestures, built on `bevy_picking`'s drag events.
+//!
+//! Elements with the `draggable` attribute receive `ondragstart`, `ondrag` and `ondragend`. Once a
+//! drag starts, the element keeps receiving `ondrag` until the pointer is released, even when the
+//! pointer leaves it. Any element can be a drop target with `ondragenter`, `ondragleave` and
+//! `ondrop`, which can read the payload the dragged element set with [`DragData::set_payload`].
+
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+
+use bevy::picking::events::{Drag, DragDrop, DragEnd, DragEnter, DragLeave, DragStart, Pointer};
+use bevy::picking::pointer::PointerButton;
+use bevy::prelude::*;
+use dioxus_core::AttributeValue;
+
+use crate::component::convert_attribute;
+use crate::error::DioxusErrorKind;
+use crate::interaction::Disabled;
+use crate::native::dispatch_targeted_event;
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    app.init_resource::<EndedDrags>()
+        .init_non_send_resource::<DragPayloads>()
+        .add_observer(start_drag)
+        .add_observer(end_drag)
+        .add_observer(forget_payload)
+        .add_systems(Last, clear_ended_payloads);
+}
+
+/// Marks an element that can be dragged.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Draggable;
+
+pub(crate) fn set_draggable(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    let draggable = match value {
+        AttributeValue::None => false,
+        value => convert_attribute::<bool>(value)?,
+    };
+
+    if draggable {
+        world.entity_mut(entity).insert(Draggable);
+    } else {
+        world.entity_mut(entity).remove::<Draggable>();
+    }
+
+    Ok(())
+}
+
+/// The payload of `ondragstart`, `ondrag` and `ondragend`.
+#[derive(Debug, Clone)]
+pub struct DragData {
+    pub button: PointerButton,
+    /// How far the pointer moved since the last `ondrag`, in logical pixels.
+    pub delta: Vec2,
+    /// How far the pointer moved since the drag started, in logical pixels.
+    pub distance: Vec2,
+    /// The position of the pointer in the window, in logical pixels.
+    pub position: Vec2,
+    dragged: Entity,
+    payloads: DragPayloads,
+}
+
+impl DragData {
+    /// Attaches a value to the drag, which drop targets can read with [`DropData::payload`].
+    pub fn set_payload<T: 'static>(&self, payload: T) {
+        self.payloads
+            .0
+            .borrow_mut()
+            .insert(self.dragged, Rc::new(payload));
+    }
+}
+
+/// The payload of `ondragenter`, `ondragleave` and `ondrop`.
+#[derive(Debug, Clone)]
+pub struct DropData {
+    pub button: PointerButton,
+    /// The entity that is being dragged.
+    pub dragged: Entity,
+    /// The position of the pointer in the window, in logical pixels.
+    pub position: Vec2,
+    payloads: DragPayloads,
+}
+
+impl DropData {
+    /// The value the dragged element attached with [`DragData::set_payload`], if it is a `T`.
+    pub fn payload<T: 'static>(&self) -> Option<Rc<T>> {
+        let payload = self.payloads.0.borrow().get(&self.dragged).cloned()?;
+        payload.downcast::<T>().ok()
+    }
+}
+
+/// The payloads of the drags in progress, by the entity being dragged.
+///
+/// It is a `NonSend` resource of the world, shared with the drag and drop events it dispatches.
+#[derive(Clone, Default)]
+pub(crate) struct DragPayloads(Rc<RefCell<HashMap<Entity, Rc<dyn Any>>>>);
+
+impl std::fmt::Debug for DragPayloads {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DragPayloads({})", self.0.borrow().len())
+    }
+}
+
+/// The observers installed for the drag listeners of an element, by event name.
+#[derive(Component, Default)]
+pub(crate) struct DragObservers(HashMap<&'static str, Entity>);
+
+pub(crate) fn add_listener(world: &mut World, entity: Entity, name: &'static str) {
+    let observer = match name {
+        "dragstart" => Observer::new(
+            move |trigger: Trigger<Pointer<DragStart>>,
+                  world: &World,
+                  payloads: NonSend<DragPayloads>| {
+                let data = DragData {
+                    button: trigger.button,
+                    delta: Vec2::ZERO,
+                    distance: Vec2::ZERO,
+                    position: trigger.pointer_location.position,
+                    dragged: trigger.target,
+                    payloads: DragPayloads::clone(&payloads),
+                };
+                dispatch_drag(world, &trigger, entity, name, data);
+            },
+        ),
+        "drag" => Observer::new(
+            move |trigger: Trigger<Pointer<Drag>>,
+                  world: &World,
+                  payloads: NonSend<DragPayloads>| {
+                let data = DragData {
+                    button: trigger.button,
+                    delta: trigger.delta,
+                    distance: trigger.distance,
+                    position: trigger.pointer_location.position,
+                    dragged: trigger.target,
+                    payloads: DragPayloads::clone(&payloads),
+                };
+                dispatch_drag(world, &trigger, entity, name, data);
+            },
+        ),
+        "dragend" => Observer::new(
+            move |trigger: Trigger<Pointer<DragEnd>>,
+                  world: &World,
+                  payloads: NonSend<DragPayloads>| {
+                let data = DragData {
+                    button: trigger.button,
+                    delta: Vec2::ZERO,
+                    distance: trigger.distance,
+                    position: trigger.pointer_location.position,
+                    dragged: trigger.target,
+                    payloads: DragPayloads::clone(&payloads),
+                };
+                dispatch_drag(world, &trigger, entity, name, data);
+            },
+        ),
+        "dragenter" => Observer::new(
+            move |trigger: Trigger<Pointer<DragEnter>>,
+                  world: &World,
+                  payloads: NonSend<DragPayloads>| {
+                let data = DropData {
+                    button: trigger.button,
+                    dragged: trigger.dragged,
+                    position: trigger.pointer_location.position,
+                    payloads: DragPayloads::clone(&payloads),
+                };
+                dispatch_drop(world, trigger.target, entity, name, data);
+            },
+        ),
+        "dragleave" => Observer::new(
+            move |trigger: Trigger<Pointer<DragLeave>>,
+                  world: &World,
+                  payloads: NonSend<DragPayloads>| {
+                let data = DropData {
+                    button: trigger.button,
+                    dragged: trigger.dragged,
+                    position: trigger.pointer_location.position,
+                    payloads: DragPayloads::clone(&payloads),
+                };
+                dispatch_drop(world, trigger.target, entity, name, data);
+            },
+        ),
+        "drop" => Observer::new(
+            move |trigger: Trigger<Pointer<DragDrop>>,
+                  world: &World,
+                  payloads: NonSend<DragPayloads>| {
+                let data = DropData {
+                    button: trigger.button,
+                    dragged: trigger.dropped,
+                    position: trigger.pointer_location.position,
+                    payloads: DragPayloads::clone(&payloads),
+                };
+                dispatch_drop(world, trigger.target, entity, name, data);
+            },
+        ),
+        _ => return,
+    };
+
+    let observer = world.spawn(observer.with_entity(entity)).id();
+    let mut entity = world.entity_mut(entity);
+    if !entity.contains::<DragObservers>() {
+        entity.insert(DragObservers::default());
+    }
+    let replaced = entity
+        .get_mut::<DragObservers>()
+        .unwrap()
+        .0
+        .insert(name, observer);
+    if let Some(replaced) = replaced {
+        world.despawn(replaced);
+    }
+}
+
+pub(crate) fn remove_listener(world: &mut World, entity: Entity, name: &str) {
+    let observer = world
+        .get_mut::<DragObservers>(entity)
+        .and_then(|mut observers| observers.0.remove(name));
+    if let Some(observer) = observer {
+        world.despawn(observer);
+    }
+}
+
+/// Dispatches a drop target event, unless the element is disabled.
+fn dispatch_drop(world: &World, target: Entity, entity: Entity, name: &str, data: DropData) {
+    if world.get::<Disabled>(entity).is_none() {
+        dispatch_targeted_event(world, target, entity, name, data, false);
+    }
+}
+
+/// Dispatches a drag source event, but only to elements that are draggable and enabled.
+fn dispatch_drag<E: std::fmt::Debug + Clone + Reflect>(
+    world: &World,
+    trigger: &Trigger<Pointer<E>>,
+    entity: Entity,
+    name: &str,
+    data: DragData,
+) {
+    if world.get::<Draggable>(entity).is_some() && world.get::<Disabled>(entity).is_none() {
+        dispatch_targeted_event(world, trigger.target, entity, name, data, false);
+    }
+}
+
+/// Drags that ended this frame, whose payloads are dropped once every drop target saw them.
+#[derive(Resource, Default)]
+struct EndedDrags(Vec<Entity>);
+
+fn start_drag(trigger: Trigger<Pointer<DragStart>>, payloads: NonSend<DragPayloads>) {
+    // Global observers run before the element's own ones, and again for every ancestor the event
+    // bubbles up to. Only forget the previous payload before the first of them.
+    if trigger.target() == trigger.target {
+        payloads.0.borrow_mut().remove(&trigger.target);
+    }
+}
+
+fn end_drag(trigger: Trigger<Pointer<DragEnd>>, mut ended: ResMut<EndedDrags>) {
+    ended.0.push(trigger.target);
+}
+
+fn clear_ended_payloads(mut ended: ResMut<EndedDrags>, payloads: NonSend<DragPayloads>) {
+    let mut payloads = payloads.0.borrow_mut();
+    for dragged in ended.0.drain(..) {
+        payloads.remove(&dragged);
+    }
+}
+
+/// Drops the payload of an element that stops being draggable or is despawned mid-drag, before its
+/// entity can be reused.
+fn forget_payload(trigger: Trigger<OnRemove, Draggable>, payloads: NonSend<DragPayloads>) {
+    payloads.0.borrow_mut().remove(&trigger.target());
+}
@@ -0,0 +1,356 @@
+//! Errors raised while applying Dioxus mutations to the world.
+//!
+//! A mutation that fails is skipped, and the renderer carries on with the rest of the frame's
+//! mutations. The failure is logged, sent as a [`DioxusError`] event, and triggered on the entity of
+//! the [`DioxusRoot`](crate::native::DioxusRoot) it belongs to, so it can be observed per root:
+//!
+//! ```rust,ignore
+//! commands
+//!     .spawn(DioxusRoot::new(App))
+//!     .observe(|trigger: Trigger<DioxusError>| warn!("UI error: {}", trigger.event()));
+//! ```
+//!
+//! Components that return an `Err` or panic are reported the same way, as a
+//! [`DioxusComponentError`], and the root shows its [`ErrorFallback`] in their place.
+
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{self, Display},
+    rc::Rc,
+};
+
+use bevy::prelude::*;
+use dioxus::prelude::Props;
+use dioxus_core::prelude::{fc_to_builder, use_hook, ErrorBoundary, ErrorContext, Properties};
+use dioxus_core::{
+    CapturedError, DynamicNode, Element, ElementId, ScopeId, Template, TemplateNode, VComponent,
+    VNode, VText,
+};
+
+use crate::component::ComponentBuilder;
+
+/// A Dioxus mutation that couldn't be applied to the world.
+#[derive(Event, Debug, Clone)]
+pub struct DioxusError {
+    /// The entity of the [`DioxusRoot`](crate::native::DioxusRoot) that rendered the mutation.
+    pub root: Entity,
+    /// The element the mutation was applied to, if it concerns one.
+    pub element_id: Option<ElementId>,
+    /// The tag of that element, if it is known.
+    pub tag: Option<&'static str>,
+    /// The attribute that was being set, if any.
+    pub attribute: Option<&'static str>,
+    pub kind: DioxusErrorKind,
+}
+
+/// What went wrong in a [`DioxusError`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DioxusErrorKind {
+    /// The [`DioxusRoot`](crate::native::DioxusRoot) entity was despawned, or lost its component.
+    MissingRoot,
+    /// No entity was rendered for the element.
+    MissingElement,
+    /// An entity the mutation refers to doesn't exist anymore.
+    MissingEntity(Entity),
+    /// The entity doesn't have a component the mutation needs.
+    MissingComponent(&'static str),
+    /// No element was declared with the tag.
+    UnknownTag,
+    /// Neither the element nor the global attributes have an attribute with this name.
+    UnknownAttribute,
+    /// The attribute value isn't of the type the attribute expects.
+    TypeMismatch { expected: &'static str },
+    /// The attribute value has the right type, but isn't valid for the attribute.
+    InvalidValue(String),
+    /// A `style` attribute declares a property it doesn't support.
+    UnsupportedStyleProperty(String),
+    /// An attribute refers to a [`token`](crate::theme::token) the theme doesn't define.
+    UnknownToken(String),
+    /// A system run to apply the mutation failed.
+    SystemFailed(String),
+    /// The mutation doesn't match the nodes the renderer has, such as asking for more nodes than
+    /// it created.
+    InvalidMutation(&'static str),
+}
+
+impl DioxusError {
+    pub fn new(root: Entity, kind: DioxusErrorKind) -> Self {
+        Self {
+            root,
+            element_id: None,
+            tag: None,
+            attribute: None,
+            kind,
+        }
+    }
+
+    pub fn with_element(mut self, element_id: ElementId) -> Self {
+        self.element_id = Some(element_id);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: Option<&'static str>) -> Self {
+        self.tag = tag;
+        self
+    }
+
+    pub fn with_attribute(mut self, attribute: &'static str) -> Self {
+        self.attribute = Some(attribute);
+        self
+    }
+}
+
+impl Display for DioxusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [root {}", self.kind, self.root)?;
+        if let Some(id) = self.element_id {
+            write!(f, ", element {}", id.0)?;
+        }
+        if let Some(tag) = self.tag {
+            write!(f, ", tag `{tag}`")?;
+        }
+        if let Some(attribute) = self.attribute {
+            write!(f, ", attribute `{attribute}`")?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl Display for DioxusErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingRoot => write!(f, "the Dioxus root doesn't exist"),
+            Self::MissingElement => write!(f, "no entity was rendered for the element"),
+            Self::MissingEntity(entity) => write!(f, "entity {entity} doesn't exist"),
+            Self::MissingComponent(component) => write!(f, "the entity has no {component}"),
+            Self::UnknownTag => write!(f, "no element is declared with this tag"),
+            Self::UnknownAttribute => write!(f, "no attribute is declared with this name"),
+            Self::TypeMismatch { expected } => write!(f, "the attribute is not a {expected}"),
+            Self::InvalidValue(reason) => write!(f, "invalid attribute value: {reason}"),
+            Self::UnsupportedStyleProperty(name) => {
+                write!(f, "unsupported style property `{name}`")
+            }
+            Self::UnknownToken(name) => write!(f, "the theme has no token `{name}`"),
+            Self::SystemFailed(reason) => write!(f, "system failed: {reason}"),
+            Self::InvalidMutation(reason) => write!(f, "invalid mutation: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for DioxusError {}
+
+impl std::error::Error for DioxusErrorKind {}
+
+/// Logs `error`, sends it as an event and triggers it on its root.
+pub(crate) fn report(world: &mut World, error: DioxusError) {
+    tracing::error!("{error}");
+
+    let root = error.root;
+    world.send_event(error.clone());
+    if world.get_entity(root).is_ok() {
+        world.trigger_targets(error, root);
+    }
+}
+
+/// A component of a [`DioxusRoot`](crate::native::DioxusRoot) returned an `Err` or panicked while
+/// rendering.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct DioxusComponentError {
+    /// The entity of the [`DioxusRoot`](crate::native::DioxusRoot) the component belongs to.
+    pub root: Entity,
+    /// The scope of the component that returned the error or panicked.
+    pub scope: ScopeId,
+    /// What went wrong: the error's `Debug` output, or its `Display` output for errors made with
+    /// `CapturedError::from_display`.
+    pub message: String,
+}
+
+impl DioxusComponentError {
+    fn from_captured(root: Entity, error: &CapturedError) -> Self {
+        // Dioxus 0.6 only exposes the error and its scope through its report, which reads
+        // `Encountered error: {error:?}\nIn scope: {scope:?}\nBacktrace: ...`, so both are read
+        // back from it, leaving the backtrace out.
+        let report = error.to_string();
+        let report = report
+            .strip_prefix("Encountered error: ")
+            .unwrap_or(&report);
+        let (message, scope) = match report.split_once("\nIn scope: ScopeId(") {
+            Some((message, rest)) => {
+                let digits = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                (message, rest[..digits].parse().map(ScopeId).ok())
+            }
+            None => (report.lines().next().unwrap_or_default(), None),
+        };
+
+        Self {
+            root,
+            scope: scope.unwrap_or(ScopeId::ROOT),
+            // Panics are reported without their payload
+            message: match message {
+                "CapturedPanic" => "the component panicked".to_string(),
+                message => message.to_string(),
+            },
+        }
+    }
+}
+
+impl Display for DioxusComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} [root {}, scope {}]",
+            self.message, self.root, self.scope.0
+        )
+    }
+}
+
+impl std::error::Error for DioxusComponentError {}
+
+/// Renders the errors of a [`DioxusRoot`](crate::native::DioxusRoot) in place of its components.
+pub type ErrorFallback = fn(&[DioxusComponentError]) -> Element;
+
+/// The [`ErrorFallback`] roots use unless they set their own: the error messages, on a red node.
+pub fn default_fallback(errors: &[DioxusComponentError]) -> Element {
+    static TEMPLATE: Template = Template {
+        roots: &[TemplateNode::Element {
+            tag: "dioxus_error",
+            namespace: None,
+            attrs: &[],
+            children: &[TemplateNode::Dynamic { id: 0 }],
+        }],
+        node_paths: &[&[0, 0]],
+        attr_paths: &[],
+    };
+
+    let message = errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([DynamicNode::Text(VText::new(message))]),
+        Box::new([]),
+    ))
+}
+
+inventory::submit! {
+    ComponentBuilder {
+        name: "dioxus_error",
+        builder: |world| {
+            world
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.6, 0.0, 0.0)),
+                ))
+                .id()
+        },
+        initializer: |_, _| Ok(()),
+        attribute_mutators: &[],
+        attribute_sets: &[],
+    }
+}
+
+/// Component errors caught while rendering the roots of a world, sent once the renderers are done
+/// with it.
+///
+/// It is a `NonSend` resource of the world, shared with the error boundary of each of its roots.
+#[derive(Clone, Default)]
+pub(crate) struct ComponentErrors(Rc<RefCell<Vec<DioxusComponentError>>>);
+
+/// Logs the queued component errors, sends them as events and triggers them on their roots.
+pub(crate) fn send_component_errors(world: &mut World) {
+    let errors = world
+        .non_send_resource::<ComponentErrors>()
+        .0
+        .borrow_mut()
+        .split_off(0);
+
+    for error in errors {
+        tracing::error!("{error}");
+
+        let root = error.root;
+        world.send_event(error.clone());
+        if world.get_entity(root).is_ok() {
+            world.trigger_targets(error, root);
+        }
+    }
+}
+
+#[derive(Props, Clone)]
+pub(crate) struct RootBoundaryProps {
+    pub(crate) root: fn() -> Element,
+    pub(crate) fallback: ErrorFallback,
+    pub(crate) entity: Entity,
+    pub(crate) errors: ComponentErrors,
+}
+
+impl PartialEq for RootBoundaryProps {
+    fn eq(&self, other: &Self) -> bool {
+        // A root never changes its component or fallback
+        self.entity == other.entity
+    }
+}
+
+/// Renders the root component of a [`DioxusRoot`](crate::native::DioxusRoot) inside an error
+/// boundary that reports its errors and shows its fallback.
+#[allow(non_snake_case)]
+pub(crate) fn RootBoundary(props: RootBoundaryProps) -> Element {
+    static TEMPLATE: Template = Template {
+        roots: &[TemplateNode::Dynamic { id: 0 }],
+        node_paths: &[&[0]],
+        attr_paths: &[],
+    };
+
+    // The boundary keeps its errors until they are cleared, and renders them all every time
+    let reported = use_hook(|| Rc::new(Cell::new(0)));
+    let RootBoundaryProps {
+        root,
+        fallback,
+        entity,
+        errors: queue,
+    } = props;
+
+    let root = VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([DynamicNode::Component(VComponent::new(root, (), "root"))]),
+        Box::new([]),
+    );
+    let boundary = fc_to_builder(ErrorBoundary)
+        .children(Ok(root))
+        .handle_error(move |context: ErrorContext| {
+            let errors = context
+                .errors()
+                .iter()
+                .map(|error| DioxusComponentError::from_captured(entity, error))
+                .collect::<Vec<_>>();
+
+            if errors.len() < reported.get() {
+                reported.set(0);
+            }
+            queue
+                .0
+                .borrow_mut()
+                .extend_from_slice(&errors[reported.get()..]);
+            reported.set(errors.len());
+
+            fallback(&errors)
+        })
+        .build()
+        .into_vcomponent(ErrorBoundary);
+
+    Ok(VNode::new(
+        None,
+        TEMPLATE,
+        Box::new([DynamicNode::Component(boundary)]),
+        Box::new([]),
+    ))
+}
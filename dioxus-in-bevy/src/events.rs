@@ -0,0 +1,161 @@
+//! The [`ElementEvent`] envelope shared by every event, and the events dispatched by the renderer
+//! itself rather than by observers declared with [`events!`](crate::events). The latter are
+//! available on every element, unless an [`events!`](crate::events) module declares an event with
+//! the same name, which then takes precedence.
+
+use std::{
+    fmt::{Debug, Display},
+    ops::Deref,
+};
+
+use bevy::prelude::*;
+use bevy::reflect::Reflect;
+use bevy::window::{FileDragAndDrop, WindowFocused, WindowResized};
+use dioxus::html::MountedData;
+use dioxus_core::ElementId;
+
+use crate::drag::{DragData, DropData};
+use crate::image::{ImageErrorData, ImageLoadData};
+use crate::layout::{ResizeData, VisibilityData};
+use crate::navigation::Activate;
+use crate::scroll::ScrollData;
+
+/// The data of every event dispatched to an element: the event's own payload, along with the
+/// entities and element it concerns.
+///
+/// It dereferences to the payload, so handlers can keep using it as they would the payload itself,
+/// while also being able to tell which entity the event started on:
+///
+/// ```rust,ignore
+/// rsx! {
+///     node {
+///         onclick: move |event| {
+///             // `target_entity` is the child that was clicked when the click bubbled up
+///             println!("clicked {} inside {}", event.data().target_entity, event.data().current_target_entity);
+///         },
+///         text { text: "Click me" }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ElementEvent<T> {
+    /// The entity the Bevy event was originally triggered on. When the event bubbled up the
+    /// hierarchy, this can be a descendant of the element that is listening to it.
+    pub target_entity: Entity,
+    /// The entity of the element the event was dispatched to.
+    pub current_target_entity: Entity,
+    /// The Dioxus element the event was dispatched to.
+    pub element_id: ElementId,
+    /// The tag of the element the event was dispatched to.
+    pub tag: Option<&'static str>,
+    /// The value produced for this event, such as the return value of an [`events!`](crate::events) closure.
+    pub payload: T,
+}
+
+impl<T> Deref for ElementEvent<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.payload
+    }
+}
+
+impl<T: Display> Display for ElementEvent<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.payload.fmt(f)
+    }
+}
+
+/// Bevy events that remember the entity they were triggered on before bubbling up the hierarchy,
+/// used to fill in [`ElementEvent::target_entity`].
+///
+/// Events that don't implement it report the entity that is listening to them.
+pub trait OriginalTarget {
+    fn original_target(&self) -> Entity;
+}
+
+impl<E: Debug + Clone + Reflect> OriginalTarget for Pointer<E> {
+    fn original_target(&self) -> Entity {
+        self.target
+    }
+}
+
+// Resolves the target of an event in `events!` observers, using `OriginalTarget` when the event
+// implements it. `(&TargetOf(..)).target_entity()` picks `ResolveOriginalTarget` when its bound
+// holds, and otherwise falls back to `ResolveTarget` through auto-ref.
+#[doc(hidden)]
+pub struct TargetOf<'a, E>(pub &'a E, pub Entity);
+
+#[doc(hidden)]
+pub trait ResolveOriginalTarget {
+    fn target_entity(&self) -> Entity;
+}
+
+impl<E: OriginalTarget> ResolveOriginalTarget for TargetOf<'_, E> {
+    fn target_entity(&self) -> Entity {
+        self.0.original_target()
+    }
+}
+
+#[doc(hidden)]
+pub trait ResolveTarget {
+    fn target_entity(&self) -> Entity;
+}
+
+impl<E> ResolveTarget for &TargetOf<'_, E> {
+    fn target_entity(&self) -> Entity {
+        self.1
+    }
+}
+
+// Declares the `rsx!` attribute of each built-in event, the `BUILT_IN` list of their names, and
+// `add_listener`/`remove_listener`, which hand them to the `add_listener`/`remove_listener` of the
+// module that dispatches them, if it keeps track of its listeners.
+macro_rules! built_in_events {
+    ($($name:ident: $payload:ty $(=> $module:ident)?,)*) => {
+        $(
+            paste::paste! {
+                crate::event_attribute!([<on $name>]: $payload);
+            }
+        )*
+
+        /// The events dispatched by the renderer, without their `on` prefix.
+        pub(crate) const BUILT_IN: &[&str] = &[$(stringify!($name)),*];
+
+        /// Starts dispatching a built-in event to an element that just registered a listener for it.
+        pub(crate) fn add_listener(world: &mut World, entity: Entity, name: &'static str) {
+            match name {
+                $($(stringify!($name) => crate::$module::add_listener(world, entity, name),)?)*
+                _ => {}
+            }
+        }
+
+        /// Stops dispatching a built-in event to an element that removed its listener.
+        pub(crate) fn remove_listener(world: &mut World, entity: Entity, name: &str) {
+            match name {
+                $($(stringify!($name) => crate::$module::remove_listener(world, entity, name),)?)*
+                _ => {}
+            }
+        }
+    };
+}
+
+built_in_events! {
+    mounted: MountedData => mounted,
+    resize: ResizeData => layout,
+    visible: VisibilityData => layout,
+    hidden: VisibilityData => layout,
+    windowresize: WindowResized => window,
+    windowfocus: WindowFocused => window,
+    filedrop: FileDragAndDrop => window,
+    dragstart: DragData => drag,
+    drag: DragData => drag,
+    dragend: DragData => drag,
+    dragenter: DropData => drag,
+    dragleave: DropData => drag,
+    drop: DropData => drag,
+    load: ImageLoadData,
+    error: ImageErrorData,
+    scroll: ScrollData => scroll,
+    activate: Activate,
+}
@@ -0,0 +1,279 @@
+//! Running Dioxus UIs without a window, for tests, servers and simulations.
+
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+
+use crate::DioxusPlugin;
+
+/// [`MinimalPlugins`] along with [`DioxusPlugin`]: everything needed to render a [`DioxusRoot`]
+/// into the world and dispatch events to it, without a window, renderer or winit event loop.
+///
+/// Layout isn't computed without `UiPlugin`, so layout events such as `onresize` won't fire unless
+/// it is added as well.
+///
+/// ```rust,ignore
+/// let mut app = App::new();
+/// app.add_plugins(HeadlessDioxusPlugins);
+/// app.world_mut().spawn(DioxusRoot::new(MyApp));
+/// app.update();
+///
+/// app.world_mut().trigger_targets(Activate, button);
+/// app.update();
+/// ```
+///
+/// [`DioxusRoot`]: crate::native::DioxusRoot
+pub struct HeadlessDioxusPlugins;
+
+impl PluginGroup for HeadlessDioxusPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add_group(MinimalPlugins)
+            .add(DioxusPlugin::default())
+    }
+}
+
+#[cfg(all(test, feature = "headless"))]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bevy::prelude::*;
+    use bevy::window::Window;
+    use dioxus::prelude::*;
+
+    use super::HeadlessDioxusPlugins;
+    use crate::error::DioxusComponentError;
+    use crate::native::{DioxusRoot, ElementTag};
+    use crate::scroll::ScrollListener;
+    use crate::stylesheet::StyleSheet;
+    use crate::text::TextStyle;
+
+    #[derive(Event)]
+    struct Poke;
+
+    #[derive(Event)]
+    struct Nudge;
+
+    crate::elements! {
+        test_elements:
+
+        probe, Node::default(), {
+            width: Val { (node: &mut Node) => { node.width = width } },
+            height: Val { (node: &mut Node) => { node.height = height } },
+        },
+
+        label with text, Text::default(), {},
+    }
+
+    crate::events! {
+        test_events:
+
+        onpoke: |_trigger: Trigger<Poke>| -> () {}
+        // Shadows the built-in `onscroll`
+        onscroll: |_trigger: Trigger<Nudge>| -> () {}
+    }
+
+    crate::dioxus_elements! {
+        elements: { test_elements }
+        events: { test_events }
+    }
+
+    static POKES: AtomicUsize = AtomicUsize::new(0);
+    static NUDGES: AtomicUsize = AtomicUsize::new(0);
+
+    #[component]
+    fn Probe() -> Element {
+        rsx! {
+            probe {
+                onpoke: move |_| _ = POKES.fetch_add(1, Ordering::SeqCst),
+                onscroll: move |_| _ = NUDGES.fetch_add(1, Ordering::SeqCst),
+            }
+        }
+    }
+
+    fn find_probe(app: &mut App) -> Entity {
+        let world = app.world_mut();
+        world
+            .query::<(Entity, &ElementTag)>()
+            .iter(world)
+            .find(|(_, tag)| tag.0 == "probe")
+            .map(|(entity, _)| entity)
+            .expect("the probe element was spawned")
+    }
+
+    fn render_probe() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(HeadlessDioxusPlugins);
+        app.world_mut().spawn(DioxusRoot::new(Probe));
+        for _ in 0..3 {
+            app.update();
+        }
+        let probe = find_probe(&mut app);
+        (app, probe)
+    }
+
+    /// Pokes the probe, and returns its size once the root rerendered.
+    fn poke(app: &mut App, probe: Entity) -> (Val, Val) {
+        app.world_mut().trigger_targets(Poke, probe);
+        for _ in 0..3 {
+            app.update();
+        }
+        let node = app.world().get::<Node>(probe).unwrap();
+        (node.width, node.height)
+    }
+
+    #[test]
+    fn dispatches_events_without_a_window() {
+        let (mut app, probe) = render_probe();
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Window>().iter(world).count(), 0);
+
+        world.trigger_targets(Poke, probe);
+        app.update();
+        assert_eq!(POKES.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn declared_events_replace_built_in_ones() {
+        let (mut app, probe) = render_probe();
+        assert!(!app.world().entity(probe).contains::<ScrollListener>());
+
+        app.world_mut().trigger_targets(Nudge, probe);
+        app.update();
+        assert_eq!(NUDGES.load(Ordering::SeqCst), 1);
+    }
+
+    #[component]
+    fn Failing() -> Element {
+        Err(dioxus_core::CapturedError::from_display("out of probes"))?
+    }
+
+    #[component]
+    fn FailingRoot() -> Element {
+        rsx! { Failing {} }
+    }
+
+    #[derive(Resource, Default)]
+    struct CaughtErrors(Vec<DioxusComponentError>);
+
+    #[test]
+    fn reports_component_errors_with_their_scope() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessDioxusPlugins);
+        app.init_resource::<CaughtErrors>();
+        let root = app
+            .world_mut()
+            .spawn(DioxusRoot::new(FailingRoot))
+            .observe(
+                |trigger: Trigger<DioxusComponentError>, mut caught: ResMut<CaughtErrors>| {
+                    caught.0.push(trigger.event().clone());
+                },
+            )
+            .id();
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let errors = &app.world().resource::<CaughtErrors>().0;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].root, root);
+        assert_eq!(errors[0].message, "out of probes");
+        // The boundary is the first scope, and the root component the second
+        assert!(errors[0].scope.0 > 1);
+    }
+
+    #[component]
+    fn Label() -> Element {
+        rsx! {
+            label { color: "red", font_size: 18, "hello" }
+        }
+    }
+
+    #[test]
+    fn sets_text_attributes_on_elements_declared_with_text() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessDioxusPlugins);
+        app.world_mut().spawn(DioxusRoot::new(Label));
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let world = app.world_mut();
+        let (_, style) = world
+            .query::<(&ElementTag, &TextStyle)>()
+            .iter(world)
+            .find(|(tag, _)| tag.0 == "label")
+            .expect("the label has a text style");
+        assert_eq!(style.color, Some(Color::srgb(1.0, 0.0, 0.0)));
+        assert_eq!(style.font_size, Some(18.0));
+    }
+
+    #[component]
+    fn Styled() -> Element {
+        let mut pokes = use_signal(|| 0);
+        let width = if pokes() == 0 { "10px" } else { "20px" };
+        rsx! {
+            probe {
+                width,
+                height: "20px",
+                style: if pokes() < 2 { "width: 100px; width: 200px" },
+                onpoke: move |_| pokes += 1,
+            }
+        }
+    }
+
+    #[test]
+    fn restores_attribute_values_once_the_style_stops_setting_them() {
+        let mut app = App::new();
+        app.add_plugins(HeadlessDioxusPlugins);
+        app.world_mut().spawn(DioxusRoot::new(Styled));
+        for _ in 0..3 {
+            app.update();
+        }
+        let probe = find_probe(&mut app);
+        let node = app.world().get::<Node>(probe).unwrap();
+        assert_eq!((node.width, node.height), (Val::Px(200.0), Val::Px(20.0)));
+
+        // The attribute changes under the style
+        assert_eq!(poke(&mut app, probe), (Val::Px(200.0), Val::Px(20.0)));
+        assert_eq!(poke(&mut app, probe), (Val::Px(20.0), Val::Px(20.0)));
+    }
+
+    #[component]
+    fn Classed() -> Element {
+        let mut pokes = use_signal(|| 0);
+        rsx! {
+            probe {
+                width: "20px",
+                height: "20px",
+                class: if pokes() == 0 { "big" },
+                onpoke: move |_| pokes += 1,
+            }
+        }
+    }
+
+    #[test]
+    fn restores_attribute_values_once_rules_stop_matching() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), HeadlessDioxusPlugins));
+        app.finish();
+        app.cleanup();
+        // The more specific rule applies, though it comes first
+        let stylesheet =
+            StyleSheet::parse("probe.big { width: 300px } .big { width: 200px; height: 50px }")
+                .unwrap();
+        let stylesheet = app
+            .world_mut()
+            .resource_mut::<Assets<StyleSheet>>()
+            .add(stylesheet);
+        app.world_mut()
+            .spawn(DioxusRoot::new(Classed).with_stylesheet(stylesheet));
+        for _ in 0..3 {
+            app.update();
+        }
+        let probe = find_probe(&mut app);
+        let node = app.world().get::<Node>(probe).unwrap();
+        assert_eq!((node.width, node.height), (Val::Px(300.0), Val::Px(50.0)));
+
+        assert_eq!(poke(&mut app, probe), (Val::Px(20.0), Val::Px(20.0)));
+    }
+}
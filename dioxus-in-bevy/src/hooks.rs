@@ -1,8 +1,14 @@
+use std::{cell::RefCell, rc::Rc, sync::Arc};
+
+use bevy::asset::{Asset, AssetEvent, AssetPath, AssetServer, Handle, UntypedAssetLoadFailedEvent};
+use bevy::ecs::event::Event;
 use bevy::ecs::system::{In, IntoSystem};
 use bevy_async_ecs::{AsyncIOSystem, AsyncWorld};
 use dioxus::prelude::*;
 
+use crate::asset::AssetState;
 use crate::root::BevyParent;
+use crate::window::HookListeners;
 
 pub fn use_bevy_world() -> Signal<Option<AsyncWorld>> {
     use_context::<Signal<Option<AsyncWorld>>>()
@@ -74,3 +80,123 @@ pub fn use_bevy_system<
 pub fn use_bevy_parent() -> Signal<BevyParent> {
     use_context::<Signal<BevyParent>>()
 }
+
+/// Calls `handler` with every `E` sent in the Bevy world while the component is mounted, for
+/// events that aren't targeted at an element, like [`AppExit`](bevy::app::AppExit) or
+/// [`WindowResized`](bevy::window::WindowResized).
+///
+/// ```rust,ignore
+/// use_window_event(move |_: AppExit| save_settings());
+/// ```
+pub fn use_window_event<E: Event + Clone>(handler: impl FnMut(E) + 'static) {
+    let callback = use_callback(handler);
+    let (listeners, id) = use_hook(|| {
+        let listeners = consume_context::<HookListeners>();
+        let id = listeners.add(callback);
+        (listeners, id)
+    });
+    use_drop(move || listeners.remove::<E>(id));
+}
+
+/// The `AssetServer` of the Bevy app rendering the component.
+///
+/// # Panics
+///
+/// If the app has no `AssetPlugin`.
+pub fn use_asset_server() -> AssetServer {
+    use_hook(|| {
+        try_consume_context::<AssetServer>()
+            .expect("`use_asset_server` needs the `AssetServer` of Bevy's `AssetPlugin`")
+    })
+}
+
+/// The asset `use_asset` loads, and the task waiting for it.
+struct LoadingAsset<A: Asset> {
+    path: AssetPath<'static>,
+    handle: Handle<A>,
+    task: Task,
+}
+
+/// Loads the asset at `path` through the `AssetServer`, and tracks whether it is loaded.
+///
+/// The state is [`Loading`](AssetState::Loading) until the asset and its dependencies are loaded,
+/// and is set again every time the asset is modified, like when it is hot reloaded, so components
+/// reading it render again. Call [`suspend`](crate::asset::SuspendAsset::suspend) on it to wait
+/// for the asset in a `SuspenseBoundary` instead:
+///
+/// ```rust,ignore
+/// #[component]
+/// fn Quests() -> Element {
+///     let quests = use_asset::<QuestTable>("data/quests.ron");
+///     match &*quests.read() {
+///         AssetState::Loaded(_) => rsx! { QuestList {} },
+///         AssetState::Loading(_) => rsx! { text { "Loading..." } },
+///         AssetState::Failed(error) => rsx! { text { "{error}" } },
+///     }
+/// }
+///
+/// #[component]
+/// fn Portrait() -> Element {
+///     let portrait = use_asset::<Image>("portraits/hero.png").suspend()?;
+///     rsx! { image { src: attr(portrait) } }
+/// }
+/// ```
+///
+/// When `path` changes, the state moves to the new asset once the component has rendered.
+pub fn use_asset<A: Asset>(path: impl Into<AssetPath<'static>>) -> Signal<AssetState<Handle<A>>> {
+    let path = path.into();
+    let asset_server = use_asset_server();
+    let mut state = use_signal(|| AssetState::of(&asset_server, asset_server.load(path.clone())));
+    let loading = use_hook(|| Rc::new(RefCell::new(None::<LoadingAsset<A>>)));
+
+    if loading
+        .borrow()
+        .as_ref()
+        .is_none_or(|loading| loading.path != path)
+    {
+        if let Some(previous) = loading.take() {
+            previous.task.cancel();
+        }
+
+        let handle = asset_server.load::<A>(path.clone());
+        let task = spawn({
+            let asset_server = asset_server.clone();
+            let handle = handle.clone();
+            async move {
+                if state.peek().handle() != Some(&handle) {
+                    state.set(AssetState::of(&asset_server, handle.clone()));
+                }
+                // Also settles when a dependency fails, which sends no event for the asset.
+                _ = asset_server.wait_for_asset(&handle).await;
+                state.set(AssetState::of(&asset_server, handle));
+            }
+        });
+        *loading.borrow_mut() = Some(LoadingAsset { path, handle, task });
+    }
+
+    let current = loading.clone();
+    use_window_event(move |event: AssetEvent<A>| {
+        let Some(handle) = current
+            .borrow()
+            .as_ref()
+            .map(|loading| loading.handle.clone())
+        else {
+            return;
+        };
+        // Loaded again after failing, or reloaded after loading.
+        if event.is_loaded_with_dependencies(&handle) || event.is_modified(&handle) {
+            state.set(AssetState::of(&asset_server, handle));
+        }
+    });
+    use_window_event(move |event: UntypedAssetLoadFailedEvent| {
+        let failed = loading
+            .borrow()
+            .as_ref()
+            .is_some_and(|loading| loading.handle.id().untyped() == event.id);
+        if failed {
+            state.set(AssetState::Failed(Arc::new(event.error)));
+        }
+    });
+
+    state
+}
@@ -0,0 +1,120 @@
+//! Images loaded from a `src` attribute, and the `onload` and `onerror` events that report how
+//! loading went.
+//!
+//! `src` is either the path of an asset, loaded through the `AssetServer`, or a `Handle<Image>`
+//! already in hand. Every time it changes, the element waits for the new image and dispatches
+//! `onload` once it is ready to draw, or `onerror` if it can't be loaded.
+
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use dioxus_core::AttributeValue;
+
+use crate::error::DioxusErrorKind;
+use crate::native::dispatch_event;
+use crate::value::{downcast_any, FromAttributeValue};
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        dispatch_image_loads.after(crate::native::process_commands),
+    );
+}
+
+/// The payload of `onload`: the image that was loaded, and its size in pixels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageLoadData {
+    pub image: Handle<Image>,
+    pub size: UVec2,
+}
+
+/// The payload of `onerror`: the image that couldn't be loaded, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageErrorData {
+    pub image: Handle<Image>,
+    pub error: String,
+}
+
+/// The value of a `src` attribute.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageSource {
+    /// The path of an image asset.
+    Path(String),
+    Handle(Handle<Image>),
+}
+
+/// Text is a path, and a `Handle<Image>` is used as is.
+impl FromAttributeValue for ImageSource {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Text(path) => Ok(Self::Path(path)),
+            value => downcast_any(&value)
+                .or_else(|| downcast_any(&value).map(Self::Handle))
+                .ok_or(DioxusErrorKind::TypeMismatch {
+                    expected: std::any::type_name::<Self>(),
+                }),
+        }
+    }
+}
+
+impl ImageSource {
+    /// The handle of the image, loading it first if it is a path.
+    #[cfg(feature = "library")]
+    pub(crate) fn load(
+        self,
+        asset_server: Option<&AssetServer>,
+    ) -> Result<Handle<Image>, DioxusErrorKind> {
+        match self {
+            Self::Path(path) => asset_server
+                .map(|server| server.load(&path))
+                .ok_or_else(|| {
+                    DioxusErrorKind::InvalidValue(format!(
+                        "`{path}` can't be loaded without the `AssetPlugin`"
+                    ))
+                }),
+            Self::Handle(image) => Ok(image),
+        }
+    }
+}
+
+/// Marks an image element whose `src` changed, until `onload` or `onerror` is dispatched for it.
+#[derive(Component)]
+pub(crate) struct LoadingImage;
+
+fn dispatch_image_loads(world: &mut World) {
+    let Some(asset_server) = world.get_resource::<AssetServer>().cloned() else {
+        return;
+    };
+
+    let loading = world
+        .query_filtered::<(Entity, &ImageNode), With<LoadingImage>>()
+        .iter(world)
+        .map(|(entity, node)| (entity, node.image.clone()))
+        .collect::<Vec<_>>();
+
+    for (entity, image) in loading {
+        let size = world
+            .get_resource::<Assets<Image>>()
+            .and_then(|images| images.get(&image))
+            .map(Image::size);
+
+        match (asset_server.get_load_state(&image), size) {
+            (Some(LoadState::Failed(error)), _) => {
+                world.entity_mut(entity).remove::<LoadingImage>();
+                let error = error.to_string();
+                dispatch_event(
+                    world,
+                    entity,
+                    "error",
+                    ImageErrorData { image, error },
+                    false,
+                );
+            }
+            // Images added to `Assets` directly have no load state.
+            (Some(LoadState::Loaded) | None, Some(size)) => {
+                world.entity_mut(entity).remove::<LoadingImage>();
+                dispatch_event(world, entity, "load", ImageLoadData { image, size }, false);
+            }
+            _ => {}
+        }
+    }
+}
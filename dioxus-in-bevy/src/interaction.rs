@@ -0,0 +1,231 @@
+//! Pseudo-state attributes: colors applied while an element is hovered, pressed or disabled.
+//!
+//! The state is read from [`Interaction`] and picking's [`PickingInteraction`] every frame, and
+//! the colors are swapped directly on the entity, so hovering doesn't re-render any component.
+
+use std::marker::PhantomData;
+
+use bevy::ecs::component::Mutable;
+use bevy::picking::hover::PickingInteraction;
+use bevy::prelude::*;
+use dioxus_core::AttributeValue;
+
+use crate::component::convert_attribute;
+use crate::error::DioxusErrorKind;
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        (
+            apply_state_colors::<BackgroundColor>,
+            apply_state_colors::<BorderColor>,
+        ),
+    );
+}
+
+/// Marks an element set as `disabled`. The events input causes aren't dispatched to it: those
+/// declared with [`events!`](crate::events), drag and drop, `onactivate` and `onscroll`. It doesn't
+/// scroll with the input, can't receive gamepad focus, and uses its `disabled_*` colors. Events
+/// reporting its state still are dispatched, like `onmounted`, `onresize`, `onvisible`,
+/// `onhidden`, `onload` and `onerror`, along with window events.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Disabled;
+
+pub(crate) fn set_disabled(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    let disabled = match value {
+        AttributeValue::None => false,
+        value => convert_attribute::<bool>(value)?,
+    };
+
+    if disabled {
+        world.entity_mut(entity).insert(Disabled);
+    } else {
+        world.entity_mut(entity).remove::<Disabled>();
+    }
+
+    Ok(())
+}
+
+/// A color component that can change with the state of its element.
+pub(crate) trait StateColor: Component<Mutability = Mutable> + Default {
+    fn color(&self) -> Color;
+    fn set_color(&mut self, color: Color);
+}
+
+impl StateColor for BackgroundColor {
+    fn color(&self) -> Color {
+        self.0
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.0 = color;
+    }
+}
+
+impl StateColor for BorderColor {
+    fn color(&self) -> Color {
+        self.0
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.0 = color;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PseudoState {
+    Hovered,
+    Pressed,
+    Disabled,
+}
+
+/// The colors of `C` for each state of an element.
+#[derive(Component)]
+pub(crate) struct StateColors<C: StateColor> {
+    hovered: Option<Color>,
+    pressed: Option<Color>,
+    disabled: Option<Color>,
+    /// The color set by the element itself, shown when no state applies.
+    base: Color,
+    /// The color this last wrote to `C`. Anything else was written by an attribute, and becomes
+    /// the new base color.
+    applied: Option<Color>,
+    marker: PhantomData<C>,
+}
+
+impl<C: StateColor> Default for StateColors<C> {
+    fn default() -> Self {
+        Self {
+            hovered: None,
+            pressed: None,
+            disabled: None,
+            base: Color::NONE,
+            applied: None,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<C: StateColor> StateColors<C> {
+    fn resolve(&self, state: Option<PseudoState>) -> Color {
+        match state {
+            Some(PseudoState::Disabled) => self.disabled,
+            Some(PseudoState::Pressed) => self.pressed.or(self.hovered),
+            Some(PseudoState::Hovered) => self.hovered,
+            None => None,
+        }
+        .unwrap_or(self.base)
+    }
+}
+
+pub(crate) fn set_state_color<C: StateColor>(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+    state: PseudoState,
+) -> Result<(), DioxusErrorKind> {
+    let color = match value {
+        AttributeValue::None => None,
+        value => Some(convert_attribute::<Color>(value)?),
+    };
+
+    let mut entity = world.entity_mut(entity);
+    if !entity.contains::<StateColors<C>>() {
+        entity.insert(StateColors::<C>::default());
+    }
+    if !entity.contains::<C>() {
+        entity.insert(C::default());
+    }
+    if !entity.contains::<Interaction>() {
+        entity.insert(Interaction::default());
+    }
+
+    let mut colors = entity.get_mut::<StateColors<C>>().unwrap();
+    match state {
+        PseudoState::Hovered => colors.hovered = color,
+        PseudoState::Pressed => colors.pressed = color,
+        PseudoState::Disabled => colors.disabled = color,
+    }
+
+    Ok(())
+}
+
+pub(crate) fn set_hover_color<C: StateColor>(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_state_color::<C>(world, entity, value, PseudoState::Hovered)
+}
+
+pub(crate) fn set_pressed_color<C: StateColor>(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_state_color::<C>(world, entity, value, PseudoState::Pressed)
+}
+
+pub(crate) fn set_disabled_color<C: StateColor>(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_state_color::<C>(world, entity, value, PseudoState::Disabled)
+}
+
+/// The state an element with these components is in, if any. Being disabled takes precedence over
+/// being pressed, which takes precedence over being hovered.
+pub(crate) fn pseudo_state(
+    interaction: Option<&Interaction>,
+    picking: Option<&PickingInteraction>,
+    disabled: bool,
+) -> Option<PseudoState> {
+    let pressed =
+        interaction == Some(&Interaction::Pressed) || picking == Some(&PickingInteraction::Pressed);
+    let hovered =
+        interaction == Some(&Interaction::Hovered) || picking == Some(&PickingInteraction::Hovered);
+
+    if disabled {
+        Some(PseudoState::Disabled)
+    } else if pressed {
+        Some(PseudoState::Pressed)
+    } else if hovered {
+        Some(PseudoState::Hovered)
+    } else {
+        None
+    }
+}
+
+type StateQuery<'w, 's, C> = Query<
+    'w,
+    's,
+    (
+        &'static mut StateColors<C>,
+        &'static mut C,
+        Option<&'static Interaction>,
+        Option<&'static PickingInteraction>,
+        Has<Disabled>,
+    ),
+>;
+
+fn apply_state_colors<C: StateColor>(mut query: StateQuery<C>) {
+    for (mut colors, mut component, interaction, picking, disabled) in &mut query {
+        let current = component.color();
+        if colors.applied != Some(current) {
+            colors.base = current;
+        }
+
+        let color = colors.resolve(pseudo_state(interaction, picking, disabled));
+        if current != color {
+            component.set_color(color);
+        }
+        if colors.applied != Some(color) {
+            colors.applied = Some(color);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Layout change events: `onresize`, `onvisible` and `onhidden`.
+//!
+//! These are dispatched after Bevy has computed the layout and visibility of the frame, so the
+//! values handed to the handlers match what will be drawn.
+
+use bevy::prelude::*;
+use bevy::render::view::VisibilitySystems;
+use bevy::ui::UiSystem;
+
+use crate::native::dispatch_event;
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        (dispatch_resize, dispatch_visibility)
+            .after(UiSystem::Layout)
+            .after(VisibilitySystems::VisibilityPropagate),
+    );
+}
+
+/// The payload of `onresize`: the new size of the node, in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResizeData {
+    pub width: f32,
+    pub height: f32,
+}
+
+/// The payload of `onvisible` and `onhidden`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisibilityData {
+    pub visible: bool,
+}
+
+/// Marks an element listening to `onresize`, with the last size it was told about.
+#[derive(Component, Default)]
+pub(crate) struct ResizeListener {
+    last_size: Option<Vec2>,
+}
+
+/// Marks an element listening to `onvisible` and/or `onhidden`, with the last visibility it was
+/// told about.
+#[derive(Component)]
+pub(crate) struct VisibilityListener {
+    visible: bool,
+    hidden: bool,
+    last_visible: bool,
+}
+
+pub(crate) fn add_listener(world: &mut World, entity: Entity, name: &str) {
+    let mut entity = world.entity_mut(entity);
+
+    match name {
+        "resize" if !entity.contains::<ResizeListener>() => {
+            entity.insert(ResizeListener::default());
+        }
+        "visible" | "hidden" => {
+            if !entity.contains::<VisibilityListener>() {
+                // Only flips from the current visibility are dispatched, not the visibility itself.
+                let last_visible = entity
+                    .get::<InheritedVisibility>()
+                    .is_some_and(|visibility| visibility.get());
+                entity.insert(VisibilityListener {
+                    visible: false,
+                    hidden: false,
+                    last_visible,
+                });
+            }
+            let mut listener = entity.get_mut::<VisibilityListener>().unwrap();
+            if name == "visible" {
+                listener.visible = true;
+            } else {
+                listener.hidden = true;
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn remove_listener(world: &mut World, entity: Entity, name: &str) {
+    let Ok(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+
+    match name {
+        "resize" => {
+            entity.remove::<ResizeListener>();
+        }
+        "visible" | "hidden" => {
+            let Some(mut listener) = entity.get_mut::<VisibilityListener>() else {
+                return;
+            };
+            if name == "visible" {
+                listener.visible = false;
+            } else {
+                listener.hidden = false;
+            }
+            if !listener.visible && !listener.hidden {
+                entity.remove::<VisibilityListener>();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn dispatch_resize(world: &mut World) {
+    let mut query = world.query_filtered::<
+        (Entity, &ComputedNode, &mut ResizeListener),
+        Or<(Changed<ComputedNode>, Added<ResizeListener>)>,
+    >();
+
+    let mut resized = Vec::new();
+    for (entity, node, mut listener) in query.iter_mut(world) {
+        let size = node.size() * node.inverse_scale_factor();
+        if listener.last_size != Some(size) {
+            listener.last_size = Some(size);
+            resized.push((entity, size));
+        }
+    }
+
+    for (entity, size) in resized {
+        let data = ResizeData {
+            width: size.x,
+            height: size.y,
+        };
+        dispatch_event(world, entity, "resize", data, false);
+    }
+}
+
+fn dispatch_visibility(world: &mut World) {
+    let mut query = world.query_filtered::<
+        (Entity, &InheritedVisibility, &mut VisibilityListener),
+        Changed<InheritedVisibility>,
+    >();
+
+    let mut flipped = Vec::new();
+    for (entity, visibility, mut listener) in query.iter_mut(world) {
+        let visible = visibility.get();
+        if listener.last_visible != visible {
+            listener.last_visible = visible;
+            flipped.push((entity, visible));
+        }
+    }
+
+    for (entity, visible) in flipped {
+        let name = if visible { "visible" } else { "hidden" };
+        dispatch_event(world, entity, name, VisibilityData { visible }, false);
+    }
+}
@@ -1,27 +1,95 @@
+// Lets the macros, which name the crate `dioxus_in_bevy`, expand inside it too.
+extern crate self as dioxus_in_bevy;
+
 pub use dioxus_core;
 pub use generational_box;
 pub use inventory;
 
+pub mod asset;
+pub mod attributes;
 pub mod component;
+pub mod drag;
+pub mod error;
+pub mod events;
+#[cfg(feature = "headless")]
+pub mod headless;
 pub mod hooks;
+pub mod image;
+pub mod interaction;
+pub mod layout;
+#[cfg(feature = "library")]
+mod library_elements;
 pub mod macros;
+pub mod mounted;
 #[cfg(feature = "web")]
 pub mod web_node;
 
+/// A standard set of elements for Bevy UI, enabled with the `library` feature.
+///
+/// Every element but `span` and `b`, which are [`TextSpan`](bevy::text::TextSpan)s, is a [`Node`],
+/// and accepts an attribute for each of its fields, named after the field (`width`,
+/// `flex_direction`, `grid_template_columns`, `margin`, ...), along with `background_color`,
+/// `border_color`, `border_radius`, `outline_width`, `outline_offset`, `outline_color`, `z_index`
+/// and `box_shadow`. Removing an attribute resets just that field to the element's default.
+///
+/// Compose it with your own elements in [`dioxus_elements!`], like any module declared with
+/// [`elements!`](crate::elements):
+///
+/// ```rust,ignore
+/// use dioxus_in_bevy::library;
+///
+/// dioxus_in_bevy::dioxus_elements! {
+///     elements: { library, my_elements }
+///     events: { my_events }
+/// }
+/// ```
+#[cfg(feature = "library")]
+pub use library_elements::library;
+
 mod history;
 pub mod native;
+pub mod navigation;
 mod renderers;
 mod root;
+pub mod scroll;
+pub mod style;
+pub mod stylesheet;
+pub mod text;
+pub mod theme;
+pub mod value;
+#[cfg(feature = "library")]
+pub mod virtual_list;
+mod window;
 
 pub mod prelude {
     pub use super::DioxusPlugin;
+    pub use crate::asset::{AssetState, SuspendAsset};
     pub use crate::component::attr;
+    pub use crate::drag::{DragData, Draggable, DropData};
+    pub use crate::error::{DioxusComponentError, DioxusError, DioxusErrorKind, ErrorFallback};
+    pub use crate::events::{ElementEvent, OriginalTarget};
+    #[cfg(feature = "headless")]
+    pub use crate::headless::HeadlessDioxusPlugins;
     pub use crate::hooks::*;
+    pub use crate::image::{ImageErrorData, ImageLoadData};
+    pub use crate::interaction::Disabled;
+    pub use crate::layout::{ResizeData, VisibilityData};
     pub use crate::macros::elements;
     pub use crate::macros::events;
+    pub use crate::mounted::BevyElement;
+    pub use crate::navigation::{Activate, NavGroup};
     pub use crate::root::{BevyApp, BevyParent, DioxusNode};
+    pub use crate::scroll::ScrollData;
+    pub use crate::style::InlineStyle;
+    pub use crate::stylesheet::{Classes, StyleSheet};
+    pub use crate::text::{FontFaces, FontWeight};
+    pub use crate::theme::{token, Theme};
+    pub use crate::value::FromAttributeValue;
+    #[cfg(feature = "library")]
+    pub use crate::virtual_list::VirtualList;
     pub use dioxus_in_bevy_macros::bevy_component;
     pub use dioxus_in_bevy_macros::create_all_elements;
+    pub use dioxus_in_bevy_macros::create_all_events;
 }
 
 pub use dioxus_in_bevy_macros::bevy_component;
@@ -52,6 +120,15 @@ impl Plugin for DioxusPlugin {
 
         app.add_plugins(AsyncEcsPlugin)
             .add_plugins(native::setup_plugin)
+            .add_plugins(layout::setup_plugin)
+            .add_plugins(navigation::setup_plugin)
+            .add_plugins(window::setup_plugin)
+            .add_plugins(drag::setup_plugin)
+            .add_plugins(interaction::setup_plugin)
+            .add_plugins(theme::setup_plugin)
+            .add_plugins(text::setup_plugin)
+            .add_plugins(image::setup_plugin)
+            .add_plugins(scroll::setup_plugin)
             .add_plugins(root::setup_plugin);
 
         #[cfg(feature = "web")]
@@ -61,4 +138,9 @@ impl Plugin for DioxusPlugin {
             }
         }
     }
+
+    fn finish(&self, app: &mut App) {
+        // Needs the `AssetServer`, which is only there once every plugin was built
+        stylesheet::setup_plugin(app);
+    }
 }
@@ -0,0 +1,240 @@
+//! Declares [`library`](crate::library), the standard element set enabled by the `library`
+//! feature.
+
+use bevy::prelude::*;
+use bevy::ui::OverflowClipBox;
+
+use crate::image::{ImageSource, LoadingImage};
+use crate::text::{FontWeight, TextStyle};
+
+/// The outline inserted by the first `outline_*` attribute set on an element, which has none.
+const NO_OUTLINE: Outline = Outline::new(Val::ZERO, Val::ZERO, Color::NONE);
+
+// Declares the library with `elements!`, adding the attributes of every `Node` field, of its
+// colors, and of its other styling components to each element written as
+// `name: defaults, bundle, { attributes }` (or `name with set: ...`). Removing one of those
+// attributes resets the field to its value in `defaults`.
+macro_rules! library_elements {
+    ([$($done:tt)*]) => {
+        crate::elements! {
+            library:
+
+            $($done)*
+        }
+    };
+
+    ([$($done:tt)*] $name:ident $(with $set:ident)?: $defaults:expr, $bundle:expr, { $($attributes:tt)* } $(, $($rest:tt)*)?) => {
+        library_elements! {
+            [
+                $($done)*
+                $name $(with $set)?, ($defaults, $bundle), {
+                    $($attributes)*
+
+                    display: Display { (node: &mut Node) => { node.display = display } @remove: (node: &mut Node) => { node.display = $defaults.display } },
+                    box_sizing: BoxSizing { (node: &mut Node) => { node.box_sizing = box_sizing } @remove: (node: &mut Node) => { node.box_sizing = $defaults.box_sizing } },
+                    position_type: PositionType { (node: &mut Node) => { node.position_type = position_type } @remove: (node: &mut Node) => { node.position_type = $defaults.position_type } },
+                    overflow: Overflow { (node: &mut Node) => { node.overflow = overflow } @remove: (node: &mut Node) => { node.overflow = $defaults.overflow } },
+                    overflow_x: OverflowAxis { (node: &mut Node) => { node.overflow.x = overflow_x } @remove: (node: &mut Node) => { node.overflow.x = $defaults.overflow.x } },
+                    overflow_y: OverflowAxis { (node: &mut Node) => { node.overflow.y = overflow_y } @remove: (node: &mut Node) => { node.overflow.y = $defaults.overflow.y } },
+                    overflow_clip_box: OverflowClipBox { (node: &mut Node) => { node.overflow_clip_margin.visual_box = overflow_clip_box } @remove: (node: &mut Node) => { node.overflow_clip_margin.visual_box = $defaults.overflow_clip_margin.visual_box } },
+                    overflow_clip_margin: f32 { (node: &mut Node) => { node.overflow_clip_margin.margin = overflow_clip_margin } @remove: (node: &mut Node) => { node.overflow_clip_margin.margin = $defaults.overflow_clip_margin.margin } },
+                    left: Val { (node: &mut Node) => { node.left = left } @remove: (node: &mut Node) => { node.left = $defaults.left } },
+                    right: Val { (node: &mut Node) => { node.right = right } @remove: (node: &mut Node) => { node.right = $defaults.right } },
+                    top: Val { (node: &mut Node) => { node.top = top } @remove: (node: &mut Node) => { node.top = $defaults.top } },
+                    bottom: Val { (node: &mut Node) => { node.bottom = bottom } @remove: (node: &mut Node) => { node.bottom = $defaults.bottom } },
+                    width: Val { (node: &mut Node) => { node.width = width } @remove: (node: &mut Node) => { node.width = $defaults.width } },
+                    height: Val { (node: &mut Node) => { node.height = height } @remove: (node: &mut Node) => { node.height = $defaults.height } },
+                    min_width: Val { (node: &mut Node) => { node.min_width = min_width } @remove: (node: &mut Node) => { node.min_width = $defaults.min_width } },
+                    min_height: Val { (node: &mut Node) => { node.min_height = min_height } @remove: (node: &mut Node) => { node.min_height = $defaults.min_height } },
+                    max_width: Val { (node: &mut Node) => { node.max_width = max_width } @remove: (node: &mut Node) => { node.max_width = $defaults.max_width } },
+                    max_height: Val { (node: &mut Node) => { node.max_height = max_height } @remove: (node: &mut Node) => { node.max_height = $defaults.max_height } },
+                    aspect_ratio: Option<f32> { (node: &mut Node) => { node.aspect_ratio = aspect_ratio } },
+                    align_items: AlignItems { (node: &mut Node) => { node.align_items = align_items } @remove: (node: &mut Node) => { node.align_items = $defaults.align_items } },
+                    justify_items: JustifyItems { (node: &mut Node) => { node.justify_items = justify_items } @remove: (node: &mut Node) => { node.justify_items = $defaults.justify_items } },
+                    align_self: AlignSelf { (node: &mut Node) => { node.align_self = align_self } @remove: (node: &mut Node) => { node.align_self = $defaults.align_self } },
+                    justify_self: JustifySelf { (node: &mut Node) => { node.justify_self = justify_self } @remove: (node: &mut Node) => { node.justify_self = $defaults.justify_self } },
+                    align_content: AlignContent { (node: &mut Node) => { node.align_content = align_content } @remove: (node: &mut Node) => { node.align_content = $defaults.align_content } },
+                    justify_content: JustifyContent { (node: &mut Node) => { node.justify_content = justify_content } @remove: (node: &mut Node) => { node.justify_content = $defaults.justify_content } },
+                    margin: UiRect { (node: &mut Node) => { node.margin = margin } @remove: (node: &mut Node) => { node.margin = $defaults.margin } },
+                    padding: UiRect { (node: &mut Node) => { node.padding = padding } @remove: (node: &mut Node) => { node.padding = $defaults.padding } },
+                    border: UiRect { (node: &mut Node) => { node.border = border } @remove: (node: &mut Node) => { node.border = $defaults.border } },
+                    flex_direction: FlexDirection { (node: &mut Node) => { node.flex_direction = flex_direction } @remove: (node: &mut Node) => { node.flex_direction = $defaults.flex_direction } },
+                    flex_wrap: FlexWrap { (node: &mut Node) => { node.flex_wrap = flex_wrap } @remove: (node: &mut Node) => { node.flex_wrap = $defaults.flex_wrap } },
+                    flex_grow: f32 { (node: &mut Node) => { node.flex_grow = flex_grow } @remove: (node: &mut Node) => { node.flex_grow = $defaults.flex_grow } },
+                    flex_shrink: f32 { (node: &mut Node) => { node.flex_shrink = flex_shrink } @remove: (node: &mut Node) => { node.flex_shrink = $defaults.flex_shrink } },
+                    flex_basis: Val { (node: &mut Node) => { node.flex_basis = flex_basis } @remove: (node: &mut Node) => { node.flex_basis = $defaults.flex_basis } },
+                    row_gap: Val { (node: &mut Node) => { node.row_gap = row_gap } @remove: (node: &mut Node) => { node.row_gap = $defaults.row_gap } },
+                    column_gap: Val { (node: &mut Node) => { node.column_gap = column_gap } @remove: (node: &mut Node) => { node.column_gap = $defaults.column_gap } },
+                    grid_auto_flow: GridAutoFlow { (node: &mut Node) => { node.grid_auto_flow = grid_auto_flow } @remove: (node: &mut Node) => { node.grid_auto_flow = $defaults.grid_auto_flow } },
+                    grid_template_rows: Vec<RepeatedGridTrack> { (node: &mut Node) => { node.grid_template_rows = grid_template_rows } @remove: (node: &mut Node) => { node.grid_template_rows = $defaults.grid_template_rows } },
+                    grid_template_columns: Vec<RepeatedGridTrack> { (node: &mut Node) => { node.grid_template_columns = grid_template_columns } @remove: (node: &mut Node) => { node.grid_template_columns = $defaults.grid_template_columns } },
+                    grid_auto_rows: Vec<GridTrack> { (node: &mut Node) => { node.grid_auto_rows = grid_auto_rows } @remove: (node: &mut Node) => { node.grid_auto_rows = $defaults.grid_auto_rows } },
+                    grid_auto_columns: Vec<GridTrack> { (node: &mut Node) => { node.grid_auto_columns = grid_auto_columns } @remove: (node: &mut Node) => { node.grid_auto_columns = $defaults.grid_auto_columns } },
+                    grid_row: GridPlacement { (node: &mut Node) => { node.grid_row = grid_row } @remove: (node: &mut Node) => { node.grid_row = $defaults.grid_row } },
+                    grid_column: GridPlacement { (node: &mut Node) => { node.grid_column = grid_column } @remove: (node: &mut Node) => { node.grid_column = $defaults.grid_column } },
+
+                    background_color: Color { (background: &mut BackgroundColor) => { background.0 = background_color } },
+                    border_color: Color { (border: &mut BorderColor) => { border.0 = border_color } },
+                    border_radius: BorderRadius { (radius: &mut BorderRadius) => { *radius = border_radius } },
+                    border_top_left_radius: Val { (radius: &mut BorderRadius) => { radius.top_left = border_top_left_radius } @remove: (radius: &mut BorderRadius) => { radius.top_left = BorderRadius::DEFAULT.top_left } },
+                    border_top_right_radius: Val { (radius: &mut BorderRadius) => { radius.top_right = border_top_right_radius } @remove: (radius: &mut BorderRadius) => { radius.top_right = BorderRadius::DEFAULT.top_right } },
+                    border_bottom_left_radius: Val { (radius: &mut BorderRadius) => { radius.bottom_left = border_bottom_left_radius } @remove: (radius: &mut BorderRadius) => { radius.bottom_left = BorderRadius::DEFAULT.bottom_left } },
+                    border_bottom_right_radius: Val { (radius: &mut BorderRadius) => { radius.bottom_right = border_bottom_right_radius } @remove: (radius: &mut BorderRadius) => { radius.bottom_right = BorderRadius::DEFAULT.bottom_right } },
+                    outline_width: Val {
+                        <this>(outline: Option<&mut Outline>)[mut commands: Commands] => {
+                            match outline {
+                                Some(mut outline) => outline.width = outline_width,
+                                None => {
+                                    commands.entity(this).insert(Outline { width: outline_width, ..NO_OUTLINE });
+                                }
+                            }
+                        }
+                        @remove: (outline: Option<&mut Outline>) => {
+                            if let Some(mut outline) = outline {
+                                outline.width = NO_OUTLINE.width;
+                            }
+                        }
+                    },
+                    outline_offset: Val {
+                        <this>(outline: Option<&mut Outline>)[mut commands: Commands] => {
+                            match outline {
+                                Some(mut outline) => outline.offset = outline_offset,
+                                None => {
+                                    commands.entity(this).insert(Outline { offset: outline_offset, ..NO_OUTLINE });
+                                }
+                            }
+                        }
+                        @remove: (outline: Option<&mut Outline>) => {
+                            if let Some(mut outline) = outline {
+                                outline.offset = NO_OUTLINE.offset;
+                            }
+                        }
+                    },
+                    outline_color: Color {
+                        <this>(outline: Option<&mut Outline>)[mut commands: Commands] => {
+                            match outline {
+                                Some(mut outline) => outline.color = outline_color,
+                                None => {
+                                    commands.entity(this).insert(Outline { color: outline_color, ..NO_OUTLINE });
+                                }
+                            }
+                        }
+                        @remove: (outline: Option<&mut Outline>) => {
+                            if let Some(mut outline) = outline {
+                                outline.color = NO_OUTLINE.color;
+                            }
+                        }
+                    },
+                    z_index: i32 { (index: &mut ZIndex) => { index.0 = z_index } },
+                    box_shadow: BoxShadow {
+                        <this>(shadow: Option<&mut BoxShadow>)[mut commands: Commands] => {
+                            match shadow {
+                                Some(mut shadow) => *shadow = box_shadow,
+                                None => {
+                                    commands.entity(this).insert(box_shadow);
+                                }
+                            }
+                        }
+                    },
+                },
+            ]
+            $($($rest)*)?
+        }
+    };
+
+    ([$($done:tt)*] $name:ident $(with $set:ident)?, $bundle:expr, { $($attributes:tt)* } $(, $($rest:tt)*)?) => {
+        library_elements! {
+            [$($done)* $name $(with $set)?, $bundle, { $($attributes)* },]
+            $($($rest)*)?
+        }
+    };
+}
+
+library_elements! {
+    []
+
+    node: Node::DEFAULT, (), {},
+
+    text with text: Node::DEFAULT, Text::default(), {
+        text: String {
+            (text_node: &mut Text) => {
+                text_node.0 = text
+            }
+        },
+    },
+
+    image: Node::DEFAULT, ImageNode::default(), {
+        src: ImageSource {
+            <this>(image_node: &mut ImageNode)[asset_server: Option<Res<AssetServer>>, mut commands: Commands] => {
+                image_node.image = src.load(asset_server.as_deref())?;
+                commands.entity(this).insert(LoadingImage);
+            }
+            @remove: <this>(image_node: &mut ImageNode)[mut commands: Commands] => {
+                image_node.image = ImageNode::default().image;
+                commands.entity(this).remove::<LoadingImage>();
+            }
+        },
+        image_mode: NodeImageMode {
+            (image_node: &mut ImageNode) => { image_node.image_mode = image_mode }
+            @remove: (image_node: &mut ImageNode) => { image_node.image_mode = NodeImageMode::Auto }
+        },
+        atlas: Handle<TextureAtlasLayout> {
+            (image_node: &mut ImageNode) => {
+                image_node.texture_atlas.get_or_insert_with(TextureAtlas::default).layout = atlas
+            }
+            @remove: (image_node: &mut ImageNode) => { image_node.texture_atlas = None }
+        },
+        atlas_index: usize {
+            (image_node: &mut ImageNode) => {
+                image_node.texture_atlas.get_or_insert_with(TextureAtlas::default).index = atlas_index
+            }
+            @remove: (image_node: &mut ImageNode) => {
+                if let Some(atlas) = &mut image_node.texture_atlas {
+                    atlas.index = 0;
+                }
+            }
+        },
+        flip_x: bool {
+            (image_node: &mut ImageNode) => { image_node.flip_x = flip_x }
+            @remove: (image_node: &mut ImageNode) => { image_node.flip_x = false }
+        },
+        flip_y: bool {
+            (image_node: &mut ImageNode) => { image_node.flip_y = flip_y }
+            @remove: (image_node: &mut ImageNode) => { image_node.flip_y = false }
+        },
+        tint: Color {
+            (image_node: &mut ImageNode) => { image_node.color = tint }
+            @remove: (image_node: &mut ImageNode) => { image_node.color = Color::WHITE }
+        },
+    },
+
+    button: Node::DEFAULT, Button, {},
+
+    scroll: Node { overflow: Overflow::scroll(), ..Node::DEFAULT }, (), {
+        scroll_x: f32 {
+            (scroll_position: &mut ScrollPosition) => { scroll_position.offset_x = scroll_x }
+            // Stays where it is, to be scrolled by the user from there.
+            @remove: (_scroll_position: &mut ScrollPosition) => {}
+        },
+        scroll_y: f32 {
+            (scroll_position: &mut ScrollPosition) => { scroll_position.offset_y = scroll_y }
+            @remove: (_scroll_position: &mut ScrollPosition) => {}
+        },
+    },
+
+    span with text, TextSpan::default(), {
+        text: String {
+            (text_span: &mut TextSpan) => {
+                text_span.0 = text
+            }
+        },
+    },
+
+    b with text, (TextSpan::default(), TextStyle { weight: Some(FontWeight::BOLD), ..default() }), {
+        text: String {
+            (text_span: &mut TextSpan) => {
+                text_span.0 = text
+            }
+        },
+    },
+}
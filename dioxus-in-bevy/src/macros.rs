@@ -3,28 +3,38 @@ macro_rules! elements {
     (
         $module_name:ident:
 
-        $($component_name:ident, $bundle_expr:expr, {
+        $($component_name:ident $(with $attribute_set:ident)?, $bundle_expr:expr, {
             $(@init: $(<$init_self_ident:ident>)? ($($init_param_name:ident: $init_param_type:ty$(,)?)+) $([$($init_world_params:tt)*])? => { $($init_attr_body:tt)* })?$(,)?
             $(
                 $attr_name:ident: $attr_type:ty {
                    $(<$self_ident:ident>)? ($($param_name:ident: $param_type:ty$(,)?)+) $([$($world_params:tt)*])? => { $($attr_body:tt)* }
+                   $(@remove: $(<$remove_self_ident:ident>)? ($($remove_param_name:ident: $remove_param_type:ty$(,)?)+) $([$($remove_world_params:tt)*])? => { $($remove_body:tt)* })?
                 }$(,)?
             )*
         }$(,)?)*
     ) => {
         pub mod $module_name {
+            #[allow(unused_imports)]
             use super::*;
 
             pub use elements::*;
 
             pub mod elements {
+                #[allow(unused_imports)]
                 pub use super::*;
 
                 $(
                     #[allow(non_camel_case_types)]
                     #[allow(non_upper_case_globals)]
                     pub mod $component_name {
+                        #[allow(unused_imports)]
                         pub use super::*;
+                        #[allow(unused_imports)]
+                        pub use $crate::attributes::*;
+                        $(
+                            #[allow(unused_imports)]
+                            pub use $crate::attributes::sets::$attribute_set::*;
+                        )?
 
                         pub const TAG_NAME: &'static str = stringify!($component_name);
                         pub const NAME_SPACE: Option<&'static str> = None;
@@ -40,33 +50,97 @@ macro_rules! elements {
                             builder: |world| {
                                 world.spawn($bundle_expr).id()
                             },
-                            initializer: |world: &mut bevy::prelude::World, entity: bevy::ecs::entity::Entity| {
+                            // Both are unused by elements without an `@init`
+                            initializer: |#[allow(unused_variables)] world: &mut bevy::prelude::World, #[allow(unused_variables)] entity: bevy::ecs::entity::Entity| {
                                 $(
-                                    let system_id = world.register_system(move |mut query: bevy::prelude::Query<($($init_param_type),*)>$(, $($init_world_params)*)?| {
-                                        let ($(mut $init_param_name),*) = query.get_mut(entity).unwrap();
+                                    bevy::ecs::system::RunSystemOnce::run_system_once(&mut *world, move |mut query: bevy::prelude::Query<($($init_param_type,)*)>$(, $($init_world_params)*)?| -> ::core::result::Result<(), $crate::error::DioxusErrorKind> {
+                                        // Only the parameters borrowing their component mutably need to be `mut`
+                                        #[allow(unused_mut)]
+                                        let ($(mut $init_param_name,)*) = query
+                                            .get_mut(entity)
+                                            .map_err(|error| $crate::error::DioxusErrorKind::SystemFailed(error.to_string()))?;
                                         $(let $init_self_ident = entity;)?
                                         {
                                             $($init_attr_body)*
-                                        }
-                                    });
-                                    world.run_system(system_id).expect("Initialization failed");
+                                        };
+                                        ::core::result::Result::Ok(())
+                                    })
+                                    .map_err(|error| $crate::error::DioxusErrorKind::SystemFailed(error.to_string()))??;
                                 )?
+                                ::core::result::Result::Ok(())
                             },
                             attribute_mutators: &[
                                 $(
                                     (stringify!($attr_name), |world: &mut bevy::prelude::World, entity: bevy::ecs::entity::Entity, value: $crate::dioxus_core::AttributeValue| {
-                                        let system_id = world.register_system(move |bevy::prelude::In($attr_name): bevy::prelude::In<$attr_type>, mut query: bevy::prelude::Query<($($param_type),*)>$(, $($world_params)*)?| {
-                                            let ($(mut $param_name),*) = query.get_mut(entity).unwrap();
+                                        let convert = |value| {
+                                            // Only one of them applies to a given type
+                                            #[allow(unused_imports)]
+                                            use $crate::value::{ConvertAny as _, ConvertWithTrait as _};
+                                            (&$crate::value::Convert::<$attr_type>::new()).convert(value)
+                                        };
+
+                                        let remove: ::core::option::Option<$crate::component::AttributeRemover> = ::core::option::Option::None
+                                            $(.or(::core::option::Option::Some(|world: &mut bevy::prelude::World, entity: bevy::ecs::entity::Entity| {
+                                                bevy::ecs::system::RunSystemOnce::run_system_once(world, move |mut query: bevy::prelude::Query<($($remove_param_type,)*)>$(, $($remove_world_params)*)?| -> ::core::result::Result<(), $crate::error::DioxusErrorKind> {
+                                                    // Only the parameters borrowing their component mutably need to be `mut`
+                                                    #[allow(unused_mut)]
+                                                    let ($(mut $remove_param_name,)*) = query
+                                                        .get_mut(entity)
+                                                        .map_err(|error| $crate::error::DioxusErrorKind::SystemFailed(error.to_string()))?;
+                                                    $(let $remove_self_ident = entity;)?
+                                                    {
+                                                        $($remove_body)*
+                                                    };
+                                                    ::core::result::Result::Ok(())
+                                                })
+                                                .map_err(|error| $crate::error::DioxusErrorKind::SystemFailed(error.to_string()))?
+                                            })))?;
+
+                                        let converted_value: $attr_type = match value {
+                                            // The attribute was removed: run its `@remove` clause, let its type handle
+                                            // `None`, or reset the components it sets to their values in the bundle
+                                            $crate::dioxus_core::AttributeValue::None => {
+                                                if let ::core::option::Option::Some(remove) = remove {
+                                                    return remove(world, entity);
+                                                }
+                                                match convert($crate::dioxus_core::AttributeValue::None) {
+                                                    ::core::result::Result::Ok(value) => value,
+                                                    ::core::result::Result::Err(_) => {
+                                                        $crate::component::reset_to_defaults(world, entity, stringify!($component_name), |defaults, target| {
+                                                            $(
+                                                                <$param_type as $crate::component::ResetFrom>::reset_from(defaults, target);
+                                                            )*
+                                                        });
+                                                        return $crate::component::reapply_attributes(
+                                                            world,
+                                                            entity,
+                                                            stringify!($attr_name),
+                                                            &[$(<$param_type as $crate::component::ResetFrom>::written()),*],
+                                                        );
+                                                    }
+                                                }
+                                            }
+                                            value => convert(value)?,
+                                        };
+                                        bevy::ecs::system::RunSystemOnce::run_system_once_with(world, move |bevy::prelude::In($attr_name): bevy::prelude::In<$attr_type>, mut query: bevy::prelude::Query<($($param_type,)*)>$(, $($world_params)*)?| -> ::core::result::Result<(), $crate::error::DioxusErrorKind> {
+                                            // Only the parameters borrowing their component mutably need to be `mut`
+                                            #[allow(unused_mut)]
+                                            let ($(mut $param_name,)*) = query
+                                                .get_mut(entity)
+                                                .map_err(|error| $crate::error::DioxusErrorKind::SystemFailed(error.to_string()))?;
                                             $(let $self_ident = entity;)?
                                             {
                                                 $($attr_body)*
-                                            }
-                                        });
-                                        let converted_value: $attr_type = $crate::component::convert_attribute(value);
-                                        world.run_system_with(system_id, converted_value).expect("Attribute mutation failed");
+                                            };
+                                            ::core::result::Result::Ok(())
+                                        }, converted_value)
+                                        .map_err(|error| $crate::error::DioxusErrorKind::SystemFailed(error.to_string()))?
+                                    }, |component: ::core::any::TypeId| {
+                                        false $(|| <$param_type as $crate::component::ResetFrom>::written() == ::core::option::Option::Some(component))*
                                     }),
                                 )*
                             ],
+                            attribute_sets: &[$($crate::attributes::sets::$attribute_set::MUTATORS)?],
                         }
                     }
                 )*
@@ -93,75 +167,153 @@ macro_rules! elements {
     };
 }
 
+/// Declares the `rsx!` attribute function for an event named `$event_name`, whose handlers
+/// receive an `Event<ElementEvent<$event_return>>`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! event_attribute {
+    ($event_name:ident: $event_return:ty) => {
+        #[inline]
+        pub fn $event_name<__Marker>(
+            mut _f: impl $crate::dioxus_core::prelude::SuperInto<$crate::dioxus_core::prelude::EventHandler<$crate::dioxus_core::Event<$crate::events::ElementEvent<$event_return>>>, __Marker>
+        ) -> $crate::dioxus_core::Attribute {
+            let owner = <$crate::generational_box::UnsyncStorage as $crate::generational_box::AnyStorage>::owner();
+            let event_handler = $crate::dioxus_core::prelude::with_owner(owner.clone(), || _f.super_into());
+
+            $crate::dioxus_core::Attribute::new(
+                stringify!($event_name),
+                $crate::dioxus_core::AttributeValue::listener(move |e: $crate::dioxus_core::Event<$crate::events::ElementEvent<$event_return>>| {
+                    _ = &owner;
+                    event_handler.call(e);
+                }),
+                None,
+                false,
+            ).into()
+        }
+
+        #[doc(hidden)]
+        pub mod $event_name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            pub fn call_with_explicit_closure<
+                __Marker,
+                Return: $crate::dioxus_core::SpawnIfAsync<__Marker> + 'static,
+                F: FnMut($crate::dioxus_core::Event<$crate::events::ElementEvent<$event_return>>) -> Return + 'static
+            >(
+                event_handler: F,
+            ) -> $crate::dioxus_core::Attribute {
+                #[allow(deprecated)]
+                super::$event_name(event_handler)
+            }
+        }
+    };
+}
+
+/// Declares Dioxus events backed by Bevy observers.
+///
+/// Each event is either a single source,
+/// `onclick: |trigger: Trigger<Pointer<Click>>| -> () {}`, or a list of sources merged into one
+/// Dioxus event, `onpress: [|t: Trigger<Pointer<Click>>| -> () {}, |t: Trigger<Activate>| -> () {}]`.
+/// Every source of an event must return the same type.
 #[macro_export]
 macro_rules! events {
     (
         $module_name:ident:
 
-        $(
-            $event_name:ident: |$($event_param:ident: $event_type:ty$(,)?)+| -> $event_return:ty $event_body:block$(,)?
-        )*
+        $($definitions:tt)*
+    ) => {
+        $crate::events!(@parse $module_name [] $($definitions)*);
+    };
+
+    (@parse $module_name:ident [$($parsed:tt)*] , $($rest:tt)*) => {
+        $crate::events!(@parse $module_name [$($parsed)*] $($rest)*);
+    };
+
+    (@parse $module_name:ident [$($parsed:tt)*]
+        $event_name:ident: |$($event_param:ident: $event_type:ty$(,)?)+| -> $event_return:ty $event_body:block
+        $($rest:tt)*
+    ) => {
+        $crate::events!(@parse $module_name [
+            $($parsed)*
+            { $event_name: $event_return; ($($event_param: $event_type),+) -> $event_return $event_body }
+        ] $($rest)*);
+    };
+
+    (@parse $module_name:ident [$($parsed:tt)*]
+        $event_name:ident: [
+            |$($event_param:ident: $event_type:ty$(,)?)+| -> $event_return:ty $event_body:block
+            $(, |$($source_param:ident: $source_type:ty$(,)?)+| -> $source_return:ty $source_body:block)*
+            $(,)?
+        ]
+        $($rest:tt)*
     ) => {
+        $crate::events!(@parse $module_name [
+            $($parsed)*
+            {
+                $event_name: $event_return;
+                ($($event_param: $event_type),+) -> $event_return $event_body
+                $(($($source_param: $source_type),+) -> $source_return $source_body)*
+            }
+        ] $($rest)*);
+    };
+
+    (@parse $module_name:ident [$({
+        $event_name:ident: $event_return:ty;
+        $(($($event_param:ident: $event_type:ty),+) -> $source_return:ty $event_body:block)+
+    })*]) => {
         pub mod $module_name {
+            #[allow(unused_imports)]
             use super::*;
 
             $(
-                #[inline]
-                pub fn $event_name<__Marker>(
-                    mut _f: impl $crate::dioxus_core::prelude::SuperInto<$crate::dioxus_core::prelude::EventHandler<$crate::dioxus_core::Event<$event_return>>, __Marker>
-                ) -> $crate::dioxus_core::Attribute {
-                    let owner = <$crate::generational_box::UnsyncStorage as $crate::generational_box::AnyStorage>::owner();
-                    let event_handler = $crate::dioxus_core::prelude::with_owner(owner.clone(), || _f.super_into());
-
-                    $crate::dioxus_core::Attribute::new(
-                        stringify!($event_name),
-                        $crate::dioxus_core::AttributeValue::listener(move |e: $crate::dioxus_core::Event<$event_return>| {
-                            _ = &owner;
-                            event_handler.call(e.map(|e| e.clone()));
-                        }),
-                        None,
-                        false,
-                    ).into()
-                }
-
-                #[doc(hidden)]
-                pub mod $event_name {
-                    use super::*;
-
-                    pub fn call_with_explicit_closure<
-                        __Marker,
-                        Return: $crate::dioxus_core::SpawnIfAsync<__Marker> + 'static,
-                        F: FnMut($crate::dioxus_core::Event<$event_return>) -> Return + 'static
-                    >(
-                        event_handler: F,
-                    ) -> $crate::dioxus_core::Attribute {
-                        #[allow(deprecated)]
-                        super::$event_name(event_handler)
-                    }
-                }
+                $crate::event_attribute!($event_name: $event_return);
             )*
+
+            $crate::prelude::create_all_events! { $module_name $($event_name),* }
         }
 
         $(
             $crate::inventory::submit! {
                 $crate::component::EventHandlerInstaller {
                     name: stringify!($event_name),
+                    module: concat!(module_path!(), "::", stringify!($module_name)),
                     handler: |
                         world: &mut bevy::prelude::World,
                         root_entity: bevy::ecs::entity::Entity,
                         entity: bevy::ecs::entity::Entity,
                         id: $crate::dioxus_core::ElementId
                     | {
-                        world.entity_mut(entity).observe(move |$($event_param: $event_type),*, _windows: NonSend<bevy::winit::WinitWindows>| {
-                            let event_return = { $event_body };
-                            $crate::native::RENDERER_CONTEXT.with_borrow(|context| {
-                                if let Some((vdom, _)) = context.renderers.get(&root_entity) {
+                        let tag = world.get::<$crate::native::ElementTag>(entity).map(|tag| tag.0);
+                        $(
+                            // Every source is dispatched as the same Dioxus event, so they must agree on its payload.
+                            let _: std::marker::PhantomData<$event_return> = std::marker::PhantomData::<$source_return>;
+
+                            world.entity_mut(entity).observe(move |$($event_param: $event_type),*, renderer_context: bevy::prelude::NonSend<$crate::native::DioxusRendererContext>, disabled: bevy::prelude::Query<(), bevy::prelude::With<$crate::interaction::Disabled>>| {
+                                if disabled.contains(entity) {
+                                    return;
+                                }
+                                let target_entity = {
+                                    #[allow(unused_imports)]
+                                    use $crate::events::{ResolveOriginalTarget as _, ResolveTarget as _};
+                                    let trigger = &$crate::first_ident!($($event_param),+);
+                                    (&$crate::events::TargetOf(trigger.event(), trigger.target())).target_entity()
+                                };
+                                let event_return: $source_return = { $event_body };
+                                let event = $crate::events::ElementEvent {
+                                    target_entity,
+                                    current_target_entity: entity,
+                                    element_id: id,
+                                    tag,
+                                    payload: event_return,
+                                };
+                                if let Some((vdom, _)) = renderer_context.renderers.get(&root_entity) {
                                     let runtime = vdom.runtime();
                                     let event_name = if stringify!($event_name).starts_with("on") { stringify!($event_name)[2..].to_string() } else { stringify!($event_name).to_string() };
-                                    runtime.handle_event(&event_name, $crate::dioxus_core::Event::new(std::rc::Rc::new(event_return), true), id);
+                                    runtime.handle_event(&event_name, $crate::dioxus_core::Event::new(std::rc::Rc::new(event), true), id);
                                 }
                             });
-                        });
+                        )+
                     },
                 }
             }
@@ -169,5 +321,15 @@ macro_rules! events {
     };
 }
 
+/// Expands to the first identifier it is given.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! first_ident {
+    ($first:ident $(, $rest:ident)*) => {
+        $first
+    };
+}
+
 pub use elements;
+pub use event_attribute;
 pub use events;
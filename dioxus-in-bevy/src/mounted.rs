@@ -0,0 +1,249 @@
+//! Support for the `onmounted` event, backed by the element's Bevy entity.
+
+use std::{future::Future, pin::Pin};
+
+use bevy::input_focus::InputFocus;
+use bevy::prelude::*;
+use bevy_async_ecs::AsyncWorld;
+use dioxus::html::{
+    geometry::{
+        euclid::{Point2D, Size2D, Vector2D},
+        PixelsRect, PixelsSize, PixelsVector2D,
+    },
+    MountedData, MountedError, MountedResult, RenderedElementBacking, ScrollBehavior,
+};
+
+use crate::native::{dispatch_event, DioxusAsyncWorld};
+
+/// Elements that registered an `onmounted` listener during the last batch of mutations.
+#[derive(Resource, Default)]
+pub(crate) struct MountedQueue(Vec<Entity>);
+
+pub(crate) fn add_listener(world: &mut World, entity: Entity, _name: &str) {
+    world.get_resource_or_init::<MountedQueue>().0.push(entity);
+}
+
+pub(crate) fn remove_listener(world: &mut World, entity: Entity, _name: &str) {
+    if let Some(mut queue) = world.get_resource_mut::<MountedQueue>() {
+        queue.0.retain(|&queued| queued != entity);
+    }
+}
+
+/// Sends `onmounted` once the whole batch of mutations has been applied, so handlers see the
+/// element attached to its final parent.
+pub(crate) fn dispatch_mounted(world: &mut World) {
+    let Some(mut queue) = world.get_resource_mut::<MountedQueue>() else {
+        return;
+    };
+    let mounted = std::mem::take(&mut queue.0);
+
+    if mounted.is_empty() {
+        return;
+    }
+
+    let async_world = world.resource::<DioxusAsyncWorld>().0.clone();
+
+    for entity in mounted {
+        let element = BevyElement {
+            entity,
+            world: async_world.clone(),
+        };
+        dispatch_event(world, entity, "mounted", MountedData::new(element), false);
+    }
+}
+
+type NodeQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static ComputedNode,
+        &'static GlobalTransform,
+        &'static ScrollPosition,
+    ),
+>;
+
+/// The [`RenderedElementBacking`] behind the [`MountedData`] of elements rendered into the Bevy
+/// world.
+///
+/// ```rust,ignore
+/// rsx! {
+///     node {
+///         onmounted: move |event| {
+///             let element = event.data();
+///             let entity = element.downcast::<BevyElement>().unwrap().entity();
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct BevyElement {
+    entity: Entity,
+    world: AsyncWorld,
+}
+
+impl BevyElement {
+    /// The entity this element was rendered to.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    async fn run<O: Send + 'static>(&self, system: fn(In<Entity>, NodeQuery) -> O) -> O {
+        let system = self.world.register_io_system(system).await;
+        let output = system.run(self.entity).await;
+        system.unregister().await;
+        output
+    }
+}
+
+impl RenderedElementBacking for BevyElement {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn get_scroll_offset(&self) -> Pin<Box<dyn Future<Output = MountedResult<PixelsVector2D>>>> {
+        let element = self.clone();
+
+        Box::pin(async move {
+            element
+                .run(|In(entity), nodes| {
+                    let (_, _, scroll) = nodes.get(entity).ok()?;
+                    Some(Vector2D::new(
+                        scroll.offset_x as f64,
+                        scroll.offset_y as f64,
+                    ))
+                })
+                .await
+                .ok_or_else(|| missing_node(element.entity))
+        })
+    }
+
+    fn get_scroll_size(&self) -> Pin<Box<dyn Future<Output = MountedResult<PixelsSize>>>> {
+        let element = self.clone();
+
+        Box::pin(async move {
+            element
+                .run(|In(entity), nodes| {
+                    let (node, _, _) = nodes.get(entity).ok()?;
+                    let size = node.content_size() * node.inverse_scale_factor();
+                    Some(Size2D::new(size.x as f64, size.y as f64))
+                })
+                .await
+                .ok_or_else(|| missing_node(element.entity))
+        })
+    }
+
+    fn get_client_rect(&self) -> Pin<Box<dyn Future<Output = MountedResult<PixelsRect>>>> {
+        let element = self.clone();
+
+        Box::pin(async move {
+            element
+                .run(|In(entity), nodes| {
+                    let (node, transform, _) = nodes.get(entity).ok()?;
+                    let rect = logical_rect(node, transform);
+                    Some(PixelsRect::new(
+                        Point2D::new(rect.min.x as f64, rect.min.y as f64),
+                        Size2D::new(rect.width() as f64, rect.height() as f64),
+                    ))
+                })
+                .await
+                .ok_or_else(|| missing_node(element.entity))
+        })
+    }
+
+    fn scroll_to(
+        &self,
+        _behavior: ScrollBehavior,
+    ) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        let entity = self.entity;
+        let world = self.world.clone();
+
+        Box::pin(async move {
+            world
+                .apply(move |world: &mut World| scroll_into_view(world, entity))
+                .await;
+            Ok(())
+        })
+    }
+
+    fn set_focus(&self, focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
+        let entity = self.entity;
+        let world = self.world.clone();
+
+        Box::pin(async move {
+            world
+                .apply(move |world: &mut World| {
+                    let mut input_focus = world.resource_mut::<InputFocus>();
+                    if focus {
+                        input_focus.set(entity);
+                    } else if input_focus.get() == Some(entity) {
+                        input_focus.clear();
+                    }
+                })
+                .await;
+            Ok(())
+        })
+    }
+}
+
+fn missing_node(entity: Entity) -> MountedError {
+    MountedError::OperationFailed(format!("{entity} is not a laid out UI node").into())
+}
+
+/// The rect of a node in logical pixels.
+fn logical_rect(node: &ComputedNode, transform: &GlobalTransform) -> Rect {
+    let rect = Rect::from_center_size(transform.translation().truncate(), node.size());
+    Rect::from_corners(
+        rect.min * node.inverse_scale_factor(),
+        rect.max * node.inverse_scale_factor(),
+    )
+}
+
+/// Adjusts the [`ScrollPosition`] of every scrolling ancestor so that `entity` becomes visible.
+fn scroll_into_view(world: &mut World, entity: Entity) {
+    let mut nodes = world.query::<(&ComputedNode, &GlobalTransform)>();
+    let Ok((node, transform)) = nodes.get(world, entity) else {
+        return;
+    };
+    let mut target = logical_rect(node, transform);
+
+    let mut parents = world.query::<&ChildOf>();
+    let ancestors = parents
+        .query(world)
+        .iter_ancestors(entity)
+        .collect::<Vec<_>>();
+
+    let mut containers =
+        world.query::<(&Node, &ComputedNode, &GlobalTransform, &mut ScrollPosition)>();
+    for ancestor in ancestors {
+        let Ok((style, node, transform, mut scroll)) = containers.get_mut(world, ancestor) else {
+            continue;
+        };
+
+        let viewport = logical_rect(node, transform);
+        let before = Vec2::new(scroll.offset_x, scroll.offset_y);
+
+        if style.overflow.x == OverflowAxis::Scroll {
+            scroll.offset_x +=
+                scroll_delta(viewport.min.x, viewport.max.x, target.min.x, target.max.x);
+        }
+        if style.overflow.y == OverflowAxis::Scroll {
+            scroll.offset_y +=
+                scroll_delta(viewport.min.y, viewport.max.y, target.min.y, target.max.y);
+        }
+
+        // Everything inside this container moves with it, including the target
+        let moved = Vec2::new(scroll.offset_x, scroll.offset_y) - before;
+        target = Rect::from_corners(target.min - moved, target.max - moved);
+    }
+}
+
+/// How far a viewport has to scroll along one axis so the target range is inside it.
+fn scroll_delta(view_min: f32, view_max: f32, target_min: f32, target_max: f32) -> f32 {
+    if target_min < view_min || target_max - target_min > view_max - view_min {
+        target_min - view_min
+    } else if target_max > view_max {
+        target_max - view_max
+    } else {
+        0.0
+    }
+}
@@ -1,46 +1,98 @@
 use std::{
     any::Any,
-    cell::RefCell,
     collections::HashMap,
+    marker::PhantomData,
     ops::{Deref, DerefMut},
     rc::Rc,
 };
 
-use crate::component::ComponentBuilder;
+use crate::component::{ComponentBuilder, ElementDefaults, EventHandlerInstaller, GlobalAttribute};
+use crate::error::{
+    ComponentErrors, DioxusComponentError, DioxusError, ErrorFallback, RootBoundary,
+    RootBoundaryProps,
+};
+use crate::events::ElementEvent;
 use crate::renderers::WorldRenderer;
+use crate::stylesheet::StyleSheet;
+use crate::window::HookListeners;
 use bevy::prelude::*;
+use bevy_async_ecs::AsyncWorld;
 #[cfg(feature = "web")]
 use dioxus::prelude::*;
-use dioxus_core::{Element, ScopeId, VirtualDom};
-
-thread_local! {
-    pub static RENDERER_CONTEXT: RefCell<DioxusRendererContext> = RefCell::new(DioxusRendererContext::default());
-}
+use dioxus_core::{Element, ElementId, Event, ScopeId, VirtualDom};
 
 pub(crate) fn setup_plugin(app: &mut App) {
     let builders = HashMap::from_iter(inventory::iter::<ComponentBuilder>().map(|b| (b.name, b)));
+    let global_attributes =
+        HashMap::from_iter(inventory::iter::<GlobalAttribute>().map(|a| (a.name, a)));
 
     app.init_non_send_resource::<DioxusCommands>()
         .init_non_send_resource::<EventChannels>()
+        .init_non_send_resource::<AttributeValues>()
+        .init_non_send_resource::<DioxusRendererContext>()
+        .init_non_send_resource::<MainThread>()
+        .init_non_send_resource::<ComponentErrors>()
         .insert_resource(DioxusBuilders(builders))
-        .add_systems(Update, (setup, render, process_commands));
+        .init_resource::<ElementDefaults>()
+        .insert_resource(DioxusGlobalAttributes(global_attributes))
+        .insert_resource(DioxusEventInstallers::collect())
+        .add_event::<DioxusError>()
+        .add_event::<DioxusComponentError>()
+        .add_observer(forget_attribute_values)
+        .add_systems(
+            Update,
+            (
+                setup,
+                render,
+                process_commands,
+                crate::error::send_component_errors
+                    .after(setup)
+                    .after(render),
+                crate::mounted::dispatch_mounted.after(process_commands),
+            ),
+        );
+
+    let async_world = AsyncWorld::from_world(app.world_mut());
+    app.insert_resource(DioxusAsyncWorld(async_world));
 }
 
 #[derive(Component)]
 pub struct DioxusRoot {
     pub(crate) root: fn() -> Element,
+    pub(crate) fallback: ErrorFallback,
     pub(crate) element_map: HashMap<dioxus_core::ElementId, Entity>,
+    pub(crate) stylesheets: Vec<Handle<StyleSheet>>,
 }
 
 impl DioxusRoot {
     pub fn new(root: fn() -> Element) -> Self {
         Self {
             root,
+            fallback: crate::error::default_fallback,
             element_map: HashMap::new(),
+            stylesheets: Vec::new(),
         }
     }
+
+    /// Renders `fallback` in place of the components when one of them returns an `Err` or
+    /// panics, instead of [`default_fallback`](crate::error::default_fallback).
+    pub fn with_fallback(mut self, fallback: ErrorFallback) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Styles the elements of the root with the rules of `stylesheet`, after those of the
+    /// stylesheets added before it.
+    pub fn with_stylesheet(mut self, stylesheet: Handle<StyleSheet>) -> Self {
+        self.stylesheets.push(stylesheet);
+        self
+    }
 }
 
+/// The `VirtualDom` of every root, along with the renderer that turns its mutations into commands.
+///
+/// It is a `NonSend` resource of the world the roots are spawned in, so each app renders its own
+/// roots, and drops them along with the world.
 #[derive(Default)]
 pub struct DioxusRendererContext {
     pub renderers: HashMap<Entity, (VirtualDom, WorldRenderer)>,
@@ -57,6 +109,91 @@ impl Deref for DioxusBuilders {
     }
 }
 
+#[derive(Resource, Default)]
+pub struct DioxusGlobalAttributes(pub HashMap<&'static str, &'static GlobalAttribute>);
+
+impl Deref for DioxusGlobalAttributes {
+    type Target = HashMap<&'static str, &'static GlobalAttribute>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The [`EventHandlerInstaller`] of every event declared with [`events!`](crate::events), keyed
+/// by the event name without its `on` prefix, as Dioxus reports it.
+#[derive(Resource, Default)]
+pub struct DioxusEventInstallers(pub HashMap<&'static str, &'static EventHandlerInstaller>);
+
+impl DioxusEventInstallers {
+    /// Collects the installers submitted to `inventory`. An event named like one dispatched by
+    /// the renderer itself replaces it.
+    ///
+    /// # Panics
+    ///
+    /// If two modules declare an event with the same name, since only one of them could ever be
+    /// installed.
+    fn collect() -> Self {
+        let mut declared: HashMap<&'static str, Vec<&'static EventHandlerInstaller>> =
+            HashMap::new();
+        for installer in inventory::iter::<EventHandlerInstaller>() {
+            let name = installer.name.strip_prefix("on").unwrap_or(installer.name);
+            declared.entry(name).or_default().push(installer);
+        }
+
+        let mut conflicts = declared
+            .values()
+            .filter(|installers| installers.len() > 1)
+            .map(|installers| {
+                let mut modules: Vec<_> = installers.iter().map(|i| i.module).collect();
+                modules.sort_unstable();
+                format!(
+                    "`{}` is declared by {}",
+                    installers[0].name,
+                    modules.join(", ")
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if !conflicts.is_empty() {
+            conflicts.sort_unstable();
+            panic!(
+                "Conflicting Dioxus events: {}. List every source of an event in a single `events!` definition instead.",
+                conflicts.join("; ")
+            );
+        }
+
+        for (name, installers) in &declared {
+            if crate::events::BUILT_IN.contains(name) {
+                tracing::debug!(
+                    "`{}` declared by {} replaces the built-in event",
+                    installers[0].name,
+                    installers[0].module
+                );
+            }
+        }
+
+        Self(
+            declared
+                .into_iter()
+                .map(|(name, installers)| (name, installers[0]))
+                .collect(),
+        )
+    }
+}
+
+impl Deref for DioxusEventInstallers {
+    type Target = HashMap<&'static str, &'static EventHandlerInstaller>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// An [`AsyncWorld`] shared by everything the renderer hands out to Dioxus, like [`crate::mounted::BevyElement`].
+#[derive(Resource, Clone)]
+pub struct DioxusAsyncWorld(pub AsyncWorld);
+
 #[derive(Default)]
 pub struct DioxusCommands(pub Vec<Box<dyn FnOnce(&mut World) + 'static>>);
 
@@ -80,9 +217,21 @@ impl DerefMut for DioxusCommands {
     }
 }
 
+/// A marker that keeps the systems and observers reading it on the main thread, where the
+/// [`DioxusRendererContext`] and other state shared with the `VirtualDom`s lives.
+#[derive(Default)]
+pub struct MainThread(PhantomData<*const ()>);
+
 #[derive(Component)]
 pub struct ElementTag(pub &'static str);
 
+/// Links an entity back to the Dioxus element it was rendered for.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DioxusElement {
+    pub root: Entity,
+    pub id: ElementId,
+}
+
 #[derive(Default)]
 pub struct EventChannels {
     pub channels: HashMap<
@@ -100,6 +249,69 @@ impl std::fmt::Debug for EventChannels {
     }
 }
 
+/// The last value set for each attribute of an element, so values Dioxus sends again unchanged
+/// don't run their mutator and trigger change detection.
+#[derive(Default)]
+pub struct AttributeValues {
+    pub values: HashMap<Entity, HashMap<&'static str, dioxus_core::AttributeValue>>,
+}
+
+impl std::fmt::Debug for AttributeValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AttributeValues({})", self.values.len())
+    }
+}
+
+fn forget_attribute_values(
+    trigger: Trigger<OnRemove, DioxusElement>,
+    mut attribute_values: NonSendMut<AttributeValues>,
+) {
+    attribute_values.values.remove(&trigger.target());
+}
+
+/// Sends an event to the listener for `name` (without the `on` prefix) on the element rendered to
+/// `entity`, wrapped in an [`ElementEvent`] that targets that same entity.
+pub(crate) fn dispatch_event<T: 'static>(
+    world: &World,
+    entity: Entity,
+    name: &str,
+    payload: T,
+    bubbles: bool,
+) {
+    dispatch_targeted_event(world, entity, entity, name, payload, bubbles);
+}
+
+/// Like [`dispatch_event`], for events that were originally triggered on `target_entity`.
+pub(crate) fn dispatch_targeted_event<T: 'static>(
+    world: &World,
+    target_entity: Entity,
+    entity: Entity,
+    name: &str,
+    payload: T,
+    bubbles: bool,
+) {
+    let Some(element) = world.get::<DioxusElement>(entity) else {
+        return;
+    };
+
+    let event = ElementEvent {
+        target_entity,
+        current_target_entity: entity,
+        element_id: element.id,
+        tag: world.get::<ElementTag>(entity).map(|tag| tag.0),
+        payload,
+    };
+
+    let Some(context) = world.get_non_send_resource::<DioxusRendererContext>() else {
+        return;
+    };
+    if let Some((vdom, _)) = context.renderers.get(&element.root) {
+        let data: Rc<dyn Any> = Rc::new(event);
+        vdom.runtime()
+            .handle_event(name, Event::new(data, bubbles), element.id);
+    }
+}
+
 fn init_history() {
     if ScopeId::ROOT
         .has_context::<Rc<dyn dioxus::prelude::document::Document>>()
@@ -126,57 +338,67 @@ fn init_history() {
     }
 }
 
-fn setup(
+pub(crate) fn setup(
     mut query: Query<(Entity, &mut DioxusRoot), Added<DioxusRoot>>,
     mut commands: Commands,
     mut dioxus_commands: NonSendMut<DioxusCommands>,
+    mut context: NonSendMut<DioxusRendererContext>,
+    hook_listeners: NonSend<HookListeners>,
+    component_errors: NonSend<ComponentErrors>,
+    asset_server: Option<Res<AssetServer>>,
 ) {
     for (entity, mut dioxus_root) in query.iter_mut() {
         let root = dioxus_root.root;
+        let fallback = dioxus_root.fallback;
 
         dioxus_root
             .element_map
             .insert(dioxus_core::ElementId(0), entity);
         commands.entity(entity).insert(Node::default());
 
-        let mut vdom = VirtualDom::new(root);
+        let mut vdom = VirtualDom::new_with_props(
+            RootBoundary,
+            RootBoundaryProps {
+                root,
+                fallback,
+                entity,
+                errors: ComponentErrors::clone(&component_errors),
+            },
+        );
         vdom.in_runtime(init_history);
+        vdom.provide_root_context(HookListeners::clone(&hook_listeners));
+        // For hooks loading assets, like `use_asset`
+        if let Some(asset_server) = &asset_server {
+            vdom.provide_root_context(AssetServer::clone(asset_server));
+        }
         let mut renderer = WorldRenderer::new(entity);
 
         vdom.rebuild(&mut renderer);
 
         dioxus_commands.extend(renderer.drain_commands());
 
-        RENDERER_CONTEXT.with_borrow_mut(|context| {
-            context.renderers.insert(entity, (vdom, renderer));
-        });
-    }
-}
-
-fn render(world: &mut World) {
-    let mut query = world.query_filtered::<Entity, With<DioxusRoot>>();
-    let mut commands_to_run = Vec::new();
-    for entity in query.iter(world) {
-        RENDERER_CONTEXT.with_borrow_mut(|context| {
-            if let Some((vdom, renderer)) = context.renderers.get_mut(&entity) {
-                if let Some(_) = futures_lite::future::block_on(futures_lite::future::poll_once(
-                    vdom.wait_for_work(),
-                )) {
-                    vdom.render_immediate(renderer);
-                    let commands = renderer.drain_commands();
-                    commands_to_run.extend(commands);
-                }
-            }
-        });
+        context.renderers.insert(entity, (vdom, renderer));
     }
+}
 
-    world
-        .get_non_send_resource_mut::<DioxusCommands>()
-        .unwrap()
-        .extend(commands_to_run);
+pub(crate) fn render(
+    roots: Query<Entity, With<DioxusRoot>>,
+    mut context: NonSendMut<DioxusRendererContext>,
+    mut dioxus_commands: NonSendMut<DioxusCommands>,
+) {
+    for entity in roots.iter() {
+        if let Some((vdom, renderer)) = context.renderers.get_mut(&entity) {
+            if let Some(_) = futures_lite::future::block_on(futures_lite::future::poll_once(
+                vdom.wait_for_work(),
+            )) {
+                vdom.render_immediate(renderer);
+                dioxus_commands.extend(renderer.drain_commands());
+            }
+        }
+    }
 }
 
-fn process_commands(world: &mut World) {
+pub(crate) fn process_commands(world: &mut World) {
     let mut dioxus_commands = world.get_non_send_resource_mut::<DioxusCommands>().unwrap();
 
     let commands = dioxus_commands.drain(..).collect::<Vec<_>>();
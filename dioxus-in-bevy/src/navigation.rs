@@ -0,0 +1,501 @@
+//! Gamepad directional navigation between focusable elements.
+//!
+//! Any element with a `tabindex` attribute can receive focus. Pressing a D-pad direction moves
+//! [`InputFocus`] to the nearest focusable node in that direction, based on the computed layout of
+//! each node, and pressing the South button triggers [`Activate`] on the focused entity, which
+//! elements receive as `onactivate`.
+//!
+//! Elements with a `navgroup` attribute group their descendants: navigation prefers to stay inside
+//! the innermost group, and the group decides what happens when it runs out of nodes in the pressed
+//! direction (see [`NavGroup`]).
+
+use std::str::FromStr;
+
+use bevy::input_focus::{tab_navigation::TabIndex, InputFocus, InputFocusVisible};
+use bevy::prelude::*;
+use dioxus_core::AttributeValue;
+
+use crate::component::convert_attribute;
+use crate::error::DioxusErrorKind;
+use crate::interaction::Disabled;
+use crate::native::dispatch_event;
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    app.init_resource::<InputFocus>()
+        .init_resource::<InputFocusVisible>()
+        .add_systems(Update, navigate_with_gamepad);
+}
+
+/// Triggered on the focused entity when the South gamepad button is pressed, and dispatched to its
+/// element as the built-in `onactivate` event.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Activate;
+
+/// A direction on the D-pad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl NavDirection {
+    const BUTTONS: [(GamepadButton, NavDirection); 4] = [
+        (GamepadButton::DPadUp, NavDirection::Up),
+        (GamepadButton::DPadDown, NavDirection::Down),
+        (GamepadButton::DPadLeft, NavDirection::Left),
+        (GamepadButton::DPadRight, NavDirection::Right),
+    ];
+
+    /// The direction as a vector in UI space, where y grows downwards.
+    fn vector(self) -> Vec2 {
+        match self {
+            NavDirection::Up => Vec2::NEG_Y,
+            NavDirection::Down => Vec2::Y,
+            NavDirection::Left => Vec2::NEG_X,
+            NavDirection::Right => Vec2::X,
+        }
+    }
+
+    fn is_horizontal(self) -> bool {
+        matches!(self, NavDirection::Left | NavDirection::Right)
+    }
+}
+
+/// Groups the focusable descendants of an element, set with the `navgroup` attribute.
+///
+/// The attribute takes a space separated list of keywords:
+///
+/// - `group`: only groups the children, navigation leaves the group when it runs out of nodes
+/// - `wrap`: wraps around to the other side of the group on both axes
+/// - `wrap-x` / `wrap-y`: wraps around on a single axis
+/// - `contain`: never moves focus outside of the group
+///
+/// ```rust,ignore
+/// rsx! {
+///     node { navgroup: "wrap-x contain",
+///         for item in items {
+///             node { tabindex: 0 }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NavGroup {
+    pub wrap_x: bool,
+    pub wrap_y: bool,
+    pub contain: bool,
+}
+
+impl NavGroup {
+    fn wraps(&self, direction: NavDirection) -> bool {
+        if direction.is_horizontal() {
+            self.wrap_x
+        } else {
+            self.wrap_y
+        }
+    }
+}
+
+impl FromStr for NavGroup {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut group = NavGroup::default();
+
+        for keyword in value.split_whitespace() {
+            match keyword {
+                "group" => {}
+                "wrap" => {
+                    group.wrap_x = true;
+                    group.wrap_y = true;
+                }
+                "wrap-x" => group.wrap_x = true,
+                "wrap-y" => group.wrap_y = true,
+                "contain" => group.contain = true,
+                other => return Err(format!("unknown navgroup keyword {other:?}")),
+            }
+        }
+
+        Ok(group)
+    }
+}
+
+pub(crate) fn set_tab_index(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    match value {
+        AttributeValue::None => {
+            world.entity_mut(entity).remove::<TabIndex>();
+        }
+        value => {
+            let index: i64 = convert_attribute(value)?;
+            let index = i32::try_from(index).map_err(|_| {
+                DioxusErrorKind::InvalidValue(format!("tabindex {index} is out of range"))
+            })?;
+            world.entity_mut(entity).insert(TabIndex(index));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn set_nav_group(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    match value {
+        AttributeValue::None => {
+            world.entity_mut(entity).remove::<NavGroup>();
+        }
+        value => {
+            let value: String = convert_attribute(value)?;
+            let group = value
+                .parse::<NavGroup>()
+                .map_err(DioxusErrorKind::InvalidValue)?;
+            world.entity_mut(entity).insert(group);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+struct Candidate {
+    entity: Entity,
+    rect: Rect,
+    tab_index: i32,
+}
+
+fn navigate_with_gamepad(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    mut focus: ResMut<InputFocus>,
+    mut focus_visible: ResMut<InputFocusVisible>,
+    focusables: Query<
+        (
+            Entity,
+            &TabIndex,
+            &ComputedNode,
+            &GlobalTransform,
+            &InheritedVisibility,
+        ),
+        Without<Disabled>,
+    >,
+    groups: Query<&NavGroup>,
+    parents: Query<&ChildOf>,
+) {
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::South) {
+            if let Some(focused) = focus.get() {
+                commands.trigger_targets(Activate, focused);
+                commands.queue(move |world: &mut World| {
+                    if world.get::<Disabled>(focused).is_some() {
+                        return;
+                    }
+                    dispatch_event(world, focused, "activate", Activate, false);
+                });
+            }
+        }
+
+        for (button, direction) in NavDirection::BUTTONS {
+            if !gamepad.just_pressed(button) {
+                continue;
+            }
+
+            let candidates = focusables
+                .iter()
+                .filter(|(_, _, node, _, visibility)| visibility.get() && !node.is_empty())
+                .map(|(entity, tab_index, node, transform, _)| Candidate {
+                    entity,
+                    rect: Rect::from_center_size(transform.translation().truncate(), node.size()),
+                    tab_index: tab_index.0,
+                })
+                .collect::<Vec<_>>();
+
+            let current = focus
+                .get()
+                .and_then(|focused| candidates.iter().find(|c| c.entity == focused));
+
+            let next = match current {
+                Some(current) => navigate_from(current, direction, &candidates, &groups, &parents),
+                None => first_focusable(&candidates),
+            };
+
+            if let Some(next) = next {
+                focus.set(next);
+                focus_visible.0 = true;
+            }
+        }
+    }
+}
+
+/// Picks the next node to focus when moving from `current` in `direction`, walking up the
+/// navigation groups of `current` until one of them yields a node or contains the navigation.
+fn navigate_from(
+    current: &Candidate,
+    direction: NavDirection,
+    candidates: &[Candidate],
+    groups: &Query<&NavGroup>,
+    parents: &Query<&ChildOf>,
+) -> Option<Entity> {
+    let others = || candidates.iter().filter(|c| c.entity != current.entity);
+
+    for ancestor in parents.iter_ancestors(current.entity) {
+        let Ok(group) = groups.get(ancestor) else {
+            continue;
+        };
+
+        let members = others()
+            .filter(|c| is_descendant_of(c.entity, ancestor, parents))
+            .collect::<Vec<_>>();
+
+        if let Some(next) = nearest_in_direction(current.rect, direction, &members) {
+            return Some(next);
+        }
+
+        if group.wraps(direction) {
+            let origin = wrapped_origin(current.rect, direction, &members);
+            if let Some(next) = nearest_in_direction(origin, direction, &members) {
+                return Some(next);
+            }
+        }
+
+        if group.contain {
+            return None;
+        }
+    }
+
+    nearest_in_direction(current.rect, direction, &others().collect::<Vec<_>>())
+}
+
+fn first_focusable(candidates: &[Candidate]) -> Option<Entity> {
+    candidates
+        .iter()
+        .min_by(|a, b| {
+            a.tab_index
+                .cmp(&b.tab_index)
+                .then(a.rect.min.y.total_cmp(&b.rect.min.y))
+                .then(a.rect.min.x.total_cmp(&b.rect.min.x))
+        })
+        .map(|c| c.entity)
+}
+
+/// Scores every candidate that lies in `direction` from `origin` by the distance along the
+/// direction plus a penalty for how far it is off-axis, and returns the best one.
+fn nearest_in_direction(
+    origin: Rect,
+    direction: NavDirection,
+    candidates: &[&Candidate],
+) -> Option<Entity> {
+    let axis = direction.vector();
+
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let offset = candidate.rect.center() - origin.center();
+            if offset.dot(axis) <= 0.0 {
+                return None;
+            }
+
+            let (along, across) = if direction.is_horizontal() {
+                (
+                    edge_gap(
+                        origin.min.x,
+                        origin.max.x,
+                        candidate.rect.min.x,
+                        candidate.rect.max.x,
+                    ),
+                    edge_gap(
+                        origin.min.y,
+                        origin.max.y,
+                        candidate.rect.min.y,
+                        candidate.rect.max.y,
+                    ),
+                )
+            } else {
+                (
+                    edge_gap(
+                        origin.min.y,
+                        origin.max.y,
+                        candidate.rect.min.y,
+                        candidate.rect.max.y,
+                    ),
+                    edge_gap(
+                        origin.min.x,
+                        origin.max.x,
+                        candidate.rect.min.x,
+                        candidate.rect.max.x,
+                    ),
+                )
+            };
+
+            Some((candidate.entity, along + across * 2.0))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+/// The gap between two ranges on the same axis, or zero if they overlap.
+fn edge_gap(a_min: f32, a_max: f32, b_min: f32, b_max: f32) -> f32 {
+    (b_min - a_max).max(a_min - b_max).max(0.0)
+}
+
+/// Moves `origin` just past the opposite edge of the group, so that searching in `direction` again
+/// finds the member on the far side.
+fn wrapped_origin(origin: Rect, direction: NavDirection, members: &[&Candidate]) -> Rect {
+    let size = origin.size();
+    let mut center = origin.center();
+
+    match direction {
+        NavDirection::Right => {
+            let edge = members
+                .iter()
+                .map(|c| c.rect.min.x)
+                .fold(f32::MAX, f32::min);
+            center.x = edge - size.x / 2.0 - 1.0;
+        }
+        NavDirection::Left => {
+            let edge = members
+                .iter()
+                .map(|c| c.rect.max.x)
+                .fold(f32::MIN, f32::max);
+            center.x = edge + size.x / 2.0 + 1.0;
+        }
+        NavDirection::Down => {
+            let edge = members
+                .iter()
+                .map(|c| c.rect.min.y)
+                .fold(f32::MAX, f32::min);
+            center.y = edge - size.y / 2.0 - 1.0;
+        }
+        NavDirection::Up => {
+            let edge = members
+                .iter()
+                .map(|c| c.rect.max.y)
+                .fold(f32::MIN, f32::max);
+            center.y = edge + size.y / 2.0 + 1.0;
+        }
+    }
+
+    Rect::from_center_size(center, size)
+}
+
+fn is_descendant_of(entity: Entity, ancestor: Entity, parents: &Query<&ChildOf>) -> bool {
+    parents.iter_ancestors(entity).any(|e| e == ancestor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(index: u32, x: f32, y: f32) -> Candidate {
+        Candidate {
+            entity: Entity::from_raw(index),
+            rect: Rect::from_center_size(Vec2::new(x, y), Vec2::splat(10.0)),
+            tab_index: 0,
+        }
+    }
+
+    fn nearest(
+        origin: &Candidate,
+        direction: NavDirection,
+        candidates: &[Candidate],
+    ) -> Option<u32> {
+        let candidates = candidates.iter().collect::<Vec<_>>();
+        nearest_in_direction(origin.rect, direction, &candidates).map(Entity::index)
+    }
+
+    #[test]
+    fn parses_nav_groups() {
+        assert_eq!("".parse::<NavGroup>(), Ok(NavGroup::default()));
+        assert_eq!("group".parse::<NavGroup>(), Ok(NavGroup::default()));
+        assert_eq!(
+            "wrap contain".parse::<NavGroup>(),
+            Ok(NavGroup {
+                wrap_x: true,
+                wrap_y: true,
+                contain: true,
+            })
+        );
+        assert_eq!(
+            " wrap-y  wrap-x ".parse::<NavGroup>(),
+            Ok(NavGroup {
+                wrap_x: true,
+                wrap_y: true,
+                contain: false,
+            })
+        );
+        assert!("wrap-z".parse::<NavGroup>().is_err());
+        assert!("Wrap".parse::<NavGroup>().is_err());
+    }
+
+    #[test]
+    fn only_considers_candidates_in_the_direction() {
+        let origin = candidate(0, 0.0, 0.0);
+        let candidates = [candidate(1, -50.0, 0.0), candidate(2, 0.0, -50.0)];
+        assert_eq!(nearest(&origin, NavDirection::Left, &candidates), Some(1));
+        assert_eq!(nearest(&origin, NavDirection::Up, &candidates), Some(2));
+        assert_eq!(nearest(&origin, NavDirection::Right, &candidates), None);
+        assert_eq!(nearest(&origin, NavDirection::Down, &candidates), None);
+    }
+
+    #[test]
+    fn prefers_aligned_candidates_over_closer_diagonal_ones() {
+        let origin = candidate(0, 0.0, 0.0);
+        // Closer along the axis, but off to the side
+        let diagonal = candidate(1, 30.0, 40.0);
+        let aligned = candidate(2, 60.0, 0.0);
+        assert_eq!(
+            nearest(&origin, NavDirection::Right, &[diagonal, aligned]),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn picks_the_closest_of_aligned_candidates() {
+        let origin = candidate(0, 0.0, 0.0);
+        let near = candidate(1, 0.0, 30.0);
+        let far = candidate(2, 0.0, 90.0);
+        assert_eq!(nearest(&origin, NavDirection::Down, &[far, near]), Some(1));
+    }
+
+    #[test]
+    fn wraps_to_the_far_side_of_the_group() {
+        let row = [
+            candidate(1, 0.0, 0.0),
+            candidate(2, 20.0, 0.0),
+            candidate(3, 40.0, 0.0),
+        ];
+        let members = row.iter().collect::<Vec<_>>();
+
+        let origin = wrapped_origin(row[2].rect, NavDirection::Right, &members);
+        assert_eq!(
+            nearest_in_direction(origin, NavDirection::Right, &members).map(Entity::index),
+            Some(1)
+        );
+
+        let origin = wrapped_origin(row[0].rect, NavDirection::Left, &members);
+        assert_eq!(
+            nearest_in_direction(origin, NavDirection::Left, &members).map(Entity::index),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn first_focusable_follows_tab_index_then_position() {
+        let mut candidates = [
+            candidate(1, 50.0, 0.0),
+            candidate(2, 0.0, 50.0),
+            candidate(3, 0.0, 0.0),
+        ];
+        assert_eq!(first_focusable(&candidates).map(Entity::index), Some(3));
+
+        candidates[2].tab_index = 1;
+        assert_eq!(first_focusable(&candidates).map(Entity::index), Some(1));
+    }
+}
@@ -3,10 +3,13 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use crate::{
-    component::EventHandlerInstaller,
-    native::{DioxusBuilders, DioxusRoot, ElementTag, EventChannels},
+use crate::component::{AttributeMutator, AttributeWrites};
+use crate::error::{DioxusError, DioxusErrorKind};
+use crate::native::{
+    AttributeValues, DioxusBuilders, DioxusElement, DioxusEventInstallers, DioxusGlobalAttributes,
+    DioxusRoot, ElementTag, EventChannels,
 };
+use crate::text::TextFragment;
 use bevy::prelude::*;
 use dioxus_core::{AttributeValue, TemplateAttribute, TemplateNode, WriteMutations};
 
@@ -25,8 +28,17 @@ impl WorldRenderer {
         }
     }
 
-    pub fn command(&mut self, command: impl FnOnce(&mut World) + 'static) {
-        self.commands.push(Box::new(command));
+    /// Queues a mutation of the world. If it fails, the error is reported and the remaining
+    /// mutations still run.
+    pub fn command(
+        &mut self,
+        command: impl FnOnce(&mut World) -> Result<(), DioxusError> + 'static,
+    ) {
+        self.commands.push(Box::new(move |world| {
+            if let Err(error) = command(world) {
+                crate::error::report(world, error);
+            }
+        }));
     }
 
     pub fn drain_commands(&mut self) -> Vec<Box<dyn FnOnce(&mut World) + 'static>> {
@@ -41,12 +53,10 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let parent = id_to_entity(world, root_entity, id);
-            let mut stack = stack.lock().unwrap();
-            let len = stack.len();
-            for child in stack.drain(len - m..) {
-                world.entity_mut(parent).add_child(child);
-            }
+            let parent = id_to_entity(world, root_entity, id)?;
+            let children = pop_nodes(&stack, root_entity, m)?;
+            world.entity_mut(parent).add_children(&children);
+            Ok(())
         });
     }
 
@@ -56,13 +66,8 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let stack = stack.lock().unwrap();
-            let mut node = stack.last().unwrap();
-            for index in path {
-                let children = world.get::<Children>(*node).unwrap();
-                node = children.get(*index as usize).unwrap();
-            }
-            assign_entity_to_id(world, root_entity, id, *node);
+            let node = node_at_path(world, &stack, root_entity, path)?;
+            assign_entity_to_id(world, root_entity, id, node)
         });
     }
 
@@ -71,17 +76,15 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let entity = id_to_entity(world, root_entity, id);
-
-            if let Some(installer) = inventory::iter::<EventHandlerInstaller>().find(|i| {
-                if i.name.starts_with("on") {
-                    &i.name[2..].to_string() == name
-                } else {
-                    i.name == name
-                }
-            }) {
-                (installer.handler)(world, root_entity, entity, id);
+            let entity = id_to_entity(world, root_entity, id)?;
+
+            // Events declared with `events!` take precedence over the built-in ones
+            let installer = world.resource::<DioxusEventInstallers>().get(name).copied();
+            match installer {
+                Some(installer) => (installer.handler)(world, root_entity, entity, id),
+                None => crate::events::add_listener(world, entity, name),
             }
+            Ok(())
         });
     }
 
@@ -91,9 +94,9 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let entity = spawn_entity(world, root_entity, id);
-            let mut stack = stack.lock().unwrap();
-            stack.push(entity);
+            let entity = spawn_entity(world, root_entity, id)?;
+            stack.lock().unwrap().push(entity);
+            Ok(())
         });
     }
 
@@ -104,10 +107,9 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let entity = spawn_entity(world, root_entity, id);
-            world.entity_mut(entity).insert(Text::new(value));
-            let mut stack = stack.lock().unwrap();
-            stack.push(entity);
+            let entity = spawn_text_fragment(world, root_entity, Some(id), value)?;
+            stack.lock().unwrap().push(entity);
+            Ok(())
         });
     }
 
@@ -117,36 +119,13 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let reference_child = id_to_entity(world, root_entity, id);
-            let parent = world.get::<ChildOf>(reference_child).unwrap().parent();
-
-            let mut stack = stack.lock().unwrap();
-            let len = stack.len();
-            let new_children = stack.drain(len - m..).collect::<Vec<_>>();
-            for child in &new_children {
-                world.entity_mut(parent).add_child(*child);
-            }
-
-            // Reorder children to ensure newly added ones appear after the reference child
-            let mut children = world.get_mut::<Children>(parent).unwrap();
-            let ref_idx = children.iter().position(|c| c == reference_child).unwrap();
-            let child_count = children.len();
-
-            // Move each new child to position after reference_child
-            for (i, _) in new_children.iter().enumerate() {
-                // Calculate the current position of this new child
-                // It will be at the end of the children list
-                let current_pos = child_count - new_children.len() + i;
-
-                // Move it to just after the reference child
-                // We need to move each child one by one, incrementing the target position
-                let target_pos = ref_idx + 1 + i;
-
-                // Swap positions until the child is in the right place
-                for pos in (target_pos..current_pos).rev() {
-                    children.swap(pos, pos + 1);
-                }
-            }
+            let reference_child = id_to_entity(world, root_entity, id)?;
+            let (parent, index) = position_in_parent(world, root_entity, id, reference_child)?;
+            let new_children = pop_nodes(&stack, root_entity, m)?;
+            world
+                .entity_mut(parent)
+                .insert_children(index + 1, &new_children);
+            Ok(())
         });
     }
 
@@ -156,29 +135,13 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let reference_child = id_to_entity(world, root_entity, id);
-            let parent = world.get::<ChildOf>(reference_child).unwrap().parent();
-
-            let mut stack = stack.lock().unwrap();
-            let len = stack.len();
-            let new_children = stack.drain(len - m..).collect::<Vec<_>>();
-            for child in &new_children {
-                world.entity_mut(parent).add_child(*child);
-            }
-
-            // Reorder children so the newly added ones appear just before the reference child
-            let mut children = world.get_mut::<Children>(parent).unwrap();
-            let ref_idx = children.iter().position(|c| c == reference_child).unwrap();
-            let child_count = children.len();
-
-            for (i, _) in new_children.iter().enumerate() {
-                let current_pos = child_count - new_children.len() + i;
-                let target_pos = ref_idx - 1 - i;
-
-                for pos in target_pos..current_pos {
-                    children.swap(pos, pos + 1);
-                }
-            }
+            let reference_child = id_to_entity(world, root_entity, id)?;
+            let (parent, index) = position_in_parent(world, root_entity, id, reference_child)?;
+            let new_children = pop_nodes(&stack, root_entity, m)?;
+            world
+                .entity_mut(parent)
+                .insert_children(index, &new_children);
+            Ok(())
         });
     }
 
@@ -199,7 +162,7 @@ impl WriteMutations for WorldRenderer {
                     &TemplateNode,
                     &mut World,
                     Option<dioxus_core::ElementId>,
-                ),
+                ) -> Result<(), DioxusError>,
             }
 
             let mut create_element = CreateElement {
@@ -214,25 +177,33 @@ impl WriteMutations for WorldRenderer {
                             attrs,
                             children,
                         } => {
-                            let entity = if let Some(builder) =
-                                world.get_resource::<DioxusBuilders>().unwrap().get(tag)
-                            {
+                            let builder = world.resource::<DioxusBuilders>().get(tag).copied();
+                            let entity = if let Some(builder) = builder {
                                 let entity = (builder.builder)(world);
-                                if let Some(id) = id {
-                                    assign_entity_to_id(world, root_entity, id, entity);
-                                }
                                 world.entity_mut(entity).insert(ElementTag(tag));
 
                                 entity
                             } else {
+                                let mut error =
+                                    DioxusError::new(root_entity, DioxusErrorKind::UnknownTag)
+                                        .with_tag(Some(tag));
+                                error.element_id = id;
+                                crate::error::report(world, error);
+
                                 world.spawn(Node::default()).id()
                             };
+                            if let Some(id) = id {
+                                assign_entity_to_id(world, root_entity, id, entity)?;
+                            }
                             stack.lock().unwrap().push(entity);
 
-                            let builder = world.get_resource::<DioxusBuilders>().unwrap().get(tag);
                             if let Some(builder) = builder {
-                                let init_mutator = { builder.initializer };
-                                init_mutator(world, entity);
+                                if let Err(kind) = (builder.initializer)(world, entity) {
+                                    let mut error =
+                                        DioxusError::new(root_entity, kind).with_tag(Some(tag));
+                                    error.element_id = id;
+                                    crate::error::report(world, error);
+                                }
                             }
 
                             for attr in attrs.iter() {
@@ -242,57 +213,56 @@ impl WriteMutations for WorldRenderer {
                                     ..
                                 } = attr
                                 {
-                                    let builder =
-                                        world.get_resource::<DioxusBuilders>().unwrap().get(tag);
-                                    if let Some(builder) = builder {
-                                        if let Some((_, mutator)) = builder
-                                            .attribute_mutators
-                                            .iter()
-                                            .find(|(name, _)| name == attr_name)
-                                        {
-                                            mutator(
-                                                world,
-                                                entity,
-                                                AttributeValue::Text(value.to_string()),
-                                            );
-                                        }
+                                    let value = AttributeValue::Text(value.to_string());
+                                    if let Err(mut error) = apply_attribute(
+                                        world,
+                                        root_entity,
+                                        entity,
+                                        Some(tag),
+                                        attr_name,
+                                        value,
+                                    ) {
+                                        error.element_id = id;
+                                        crate::error::report(world, error);
                                     }
                                 }
                             }
 
                             for child in children.iter() {
-                                (create_element.f)(create_element, child, world, None);
+                                (create_element.f)(create_element, child, world, None)?;
                             }
 
                             // Pop children off stack and parent them
-                            let m = children.len();
-                            let len = stack.lock().unwrap().len();
-                            for child in stack.lock().unwrap().drain(len - m..) {
-                                world.entity_mut(entity).add_child(child);
-                            }
+                            let children = pop_nodes(&stack, root_entity, children.len())?;
+                            world.entity_mut(entity).add_children(&children);
                         }
                         TemplateNode::Text { text } => {
-                            let entity = if let Some(id) = id {
-                                spawn_entity(world, root_entity, id)
-                            } else {
-                                world.spawn(Node::default()).id()
-                            };
-                            world.entity_mut(entity).insert(Text::new(text.to_owned()));
+                            let entity =
+                                spawn_text_fragment(world, root_entity, id, text.to_string())?;
                             stack.lock().unwrap().push(entity);
                         }
                         TemplateNode::Dynamic { .. } => {
                             let entity = if let Some(id) = id {
-                                spawn_entity(world, root_entity, id)
+                                spawn_entity(world, root_entity, id)?
                             } else {
                                 world.spawn(Node::default()).id()
                             };
                             stack.lock().unwrap().push(entity);
                         }
                     }
+
+                    Ok(())
                 },
             };
 
-            (create_element.f)(&mut create_element, &template.roots[index], world, Some(id));
+            let Some(root) = template.roots.get(index) else {
+                return Err(DioxusError::new(
+                    root_entity,
+                    DioxusErrorKind::InvalidMutation("the template has no root at this index"),
+                )
+                .with_element(id));
+            };
+            (create_element.f)(&mut create_element, root, world, Some(id))
         });
     }
 
@@ -302,23 +272,32 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let entity = id_to_entity(world, root_entity, id);
-            let mut stack = stack.lock().unwrap();
-            stack.push(entity);
+            let entity = id_to_entity(world, root_entity, id)?;
+            stack.lock().unwrap().push(entity);
+            Ok(())
         });
     }
 
-    fn remove_event_listener(&mut self, name: &'static str, _id: dioxus_core::ElementId) {
+    fn remove_event_listener(&mut self, name: &'static str, id: dioxus_core::ElementId) {
         tracing::trace!("remove_event_listener: {:?}", name);
-        // we can't remove observers
+        let root_entity = self.root_entity;
+
+        // we can't remove observers, but built-in events can stop being dispatched
+        self.command(move |world| {
+            let entity = id_to_entity(world, root_entity, id)?;
+            if !world.resource::<DioxusEventInstallers>().contains_key(name) {
+                crate::events::remove_listener(world, entity, name);
+            }
+            Ok(())
+        });
     }
 
     fn remove_node(&mut self, id: dioxus_core::ElementId) {
         tracing::trace!("remove_node: {:?}", id);
         let root_entity = self.root_entity;
         self.command(move |world| {
-            let entity = id_to_entity(world, root_entity, id);
-            assert!(world.despawn(entity));
+            let entity = id_to_entity(world, root_entity, id)?;
+            despawn(world, root_entity, entity)
         });
     }
 
@@ -328,84 +307,46 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let entity = id_to_entity(world, root_entity, id);
-            let parent = world.get::<ChildOf>(entity).unwrap().parent();
-
-            // Remove the entity from the parent's children
-            let children = world.get_mut::<Children>(parent).unwrap();
-            let idx = children.iter().position(|c| c == entity).unwrap();
-            world.entity_mut(parent).remove_children(&[entity]);
-
-            // Add the new children from the stack
-            let mut stack = stack.lock().unwrap();
-            let len = stack.len();
-            let new_children = stack.drain(len - m..).collect::<Vec<_>>();
-            // Add all new children to the parent entity
-            world.entity_mut(parent).add_children(&new_children);
-
-            // Now rearrange the children to place them at the correct position
-            let mut children = world.get_mut::<Children>(parent).unwrap();
-            let child_count = children.len();
-
-            // Move each new child to the position where the old entity was
-            for (i, _) in new_children.iter().enumerate() {
-                let current_pos = child_count - new_children.len() + i;
-                let target_pos = idx + i;
-
-                // Swap the child into position
-                for pos in (target_pos..current_pos).rev() {
-                    children.swap(pos, pos + 1);
-                }
-            }
-
-            // Despawn the replaced entity
-            assert!(world.despawn(entity));
+            let entity = id_to_entity(world, root_entity, id)?;
+            let (parent, index) = position_in_parent(world, root_entity, id, entity)?;
+
+            // Put the new nodes where the replaced one is, then despawn it
+            let new_children = pop_nodes(&stack, root_entity, m)?;
+            world
+                .entity_mut(parent)
+                .insert_children(index, &new_children);
+            despawn(world, root_entity, entity)
         });
     }
 
     fn replace_placeholder_with_nodes(&mut self, path: &'static [u8], m: usize) {
         tracing::trace!("replace_placeholder_with_nodes: {:?}", path);
         let stack = self.stack.clone();
+        let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let mut stack = stack.lock().unwrap();
-            let mut node = stack.last().unwrap();
-            for index in path {
-                let children = world.get::<Children>(*node).unwrap();
-                node = children.get(*index as usize).unwrap();
-            }
-            let node = *node;
-
-            let parent = world.get::<ChildOf>(node).unwrap().parent();
-
-            // Remove the entity from the parent's children
-            let children = world.get_mut::<Children>(parent).unwrap();
-            let idx = children.iter().position(|c| c == node).unwrap();
-            world.entity_mut(parent).remove_children(&[node]);
-
-            // Add the new children from the stack
-            let len = stack.len();
-            let new_children = stack.drain(len - m..).collect::<Vec<_>>();
-            // Add all new children to the parent entity
-            world.entity_mut(parent).add_children(&new_children);
-
-            // Now rearrange the children to place them at the correct position
-            let mut children = world.get_mut::<Children>(parent).unwrap();
-            let child_count = children.len();
-
-            // Move each new child to the position where the old entity was
-            for (i, _) in new_children.iter().enumerate() {
-                let current_pos = child_count - new_children.len() + i;
-                let target_pos = idx + i;
-
-                // Swap the child into position
-                for pos in (target_pos..current_pos).rev() {
-                    children.swap(pos, pos + 1);
-                }
-            }
+            // The path starts from the node below the new ones on the stack
+            let new_children = pop_nodes(&stack, root_entity, m)?;
+            let node = node_at_path(world, &stack, root_entity, path)?;
+            let Some((parent, index)) = world.get::<ChildOf>(node).and_then(|child_of| {
+                let parent = child_of.parent();
+                let index = world
+                    .get::<Children>(parent)?
+                    .iter()
+                    .position(|c| c == node)?;
+                Some((parent, index))
+            }) else {
+                return Err(DioxusError::new(
+                    root_entity,
+                    DioxusErrorKind::MissingComponent("ChildOf"),
+                ));
+            };
 
-            // Despawn the replaced entity
-            assert!(world.despawn(node));
+            // Put the new nodes where the placeholder is, then despawn it
+            world
+                .entity_mut(parent)
+                .insert_children(index, &new_children);
+            despawn(world, root_entity, node)
         });
     }
 
@@ -421,7 +362,7 @@ impl WriteMutations for WorldRenderer {
         let value = value.clone();
 
         self.command(move |world| {
-            let entity = id_to_entity(world, root_entity, id);
+            let entity = id_to_entity(world, root_entity, id)?;
             if let AttributeValue::Listener(event_handler) = value {
                 let mut event_channels =
                     world.get_non_send_resource_mut::<EventChannels>().unwrap();
@@ -430,20 +371,35 @@ impl WriteMutations for WorldRenderer {
                     .entry(entity)
                     .or_insert_with(HashMap::new)
                     .insert(name, event_handler);
+                return Ok(());
             }
 
-            let tag = world.get::<ElementTag>(entity).unwrap();
-            let builder = world.get_resource::<DioxusBuilders>().unwrap().get(tag.0);
+            // Skip values that didn't change since they were last set
+            let mut attribute_values = world.non_send_resource_mut::<AttributeValues>();
+            let last_values = attribute_values.values.entry(entity).or_default();
+            if last_values.get(name) == Some(&value) {
+                return Ok(());
+            }
+            last_values.remove(name);
 
-            if let Some(builder) = builder {
-                if let Some((_, mutator)) = builder
-                    .attribute_mutators
-                    .iter()
-                    .find(|(attr_name, _)| *attr_name == name)
-                {
-                    mutator(world, entity, value);
-                }
+            let tag = world.get::<ElementTag>(entity).map(|tag| tag.0);
+            let result = apply_attribute(world, root_entity, entity, tag, name, value.clone())
+                .map_err(|error| error.with_element(id));
+
+            // Tokens the theme is missing are still kept, to be resolved once a theme defines them
+            let kept = match &result {
+                Ok(()) => true,
+                Err(error) => matches!(error.kind, DioxusErrorKind::UnknownToken(_)),
+            };
+            if kept && !matches!(value, AttributeValue::None) {
+                world
+                    .non_send_resource_mut::<AttributeValues>()
+                    .values
+                    .entry(entity)
+                    .or_default()
+                    .insert(name, value);
             }
+            result
         });
     }
 
@@ -453,22 +409,110 @@ impl WriteMutations for WorldRenderer {
         let root_entity = self.root_entity;
 
         self.command(move |world| {
-            let entity = id_to_entity(world, root_entity, id);
-            let mut text = world.get_mut::<Text>(entity).unwrap();
-            text.0 = value;
+            let entity = id_to_entity(world, root_entity, id)?;
+            let mut entity = world.entity_mut(entity);
+            if let Some(mut span) = entity.get_mut::<TextSpan>() {
+                span.0 = value;
+            } else if let Some(mut text) = entity.get_mut::<Text>() {
+                text.0 = value;
+            } else {
+                return Err(DioxusError::new(
+                    root_entity,
+                    DioxusErrorKind::MissingComponent("Text"),
+                )
+                .with_element(id));
+            }
+            Ok(())
         });
     }
 }
 
-fn spawn_entity(world: &mut World, root_entity: Entity, id: dioxus_core::ElementId) -> Entity {
-    let entity = world.spawn(Node::default()).id();
+/// Finds the mutator for an attribute, preferring the element's own attributes over those of its
+/// attribute sets, and those over the global ones, along with the components it writes. Global
+/// and set attributes don't write the components the style sets.
+fn find_attribute_mutator(
+    world: &World,
+    tag: Option<&str>,
+    name: &str,
+) -> Option<(AttributeMutator, AttributeWrites)> {
+    let builder = tag.and_then(|tag| world.resource::<DioxusBuilders>().get(tag));
+
+    if let Some((_, mutator, writes)) = builder.and_then(|builder| {
+        builder
+            .attribute_mutators
+            .iter()
+            .find(|(attr_name, _, _)| *attr_name == name)
+    }) {
+        return Some((*mutator, *writes));
+    }
+
+    if let Some((_, mutator)) = builder.and_then(|builder| {
+        builder
+            .attribute_sets
+            .iter()
+            .flat_map(|set| set.iter())
+            .find(|(attr_name, _)| *attr_name == name)
+    }) {
+        return Some((*mutator, |_| false));
+    }
+
     world
-        .entity_mut(root_entity)
-        .get_mut::<DioxusRoot>()
-        .unwrap()
-        .element_map
-        .insert(id, entity);
-    entity
+        .resource::<DioxusGlobalAttributes>()
+        .get(name)
+        .map(|attribute| (attribute.mutator, (|_| false) as AttributeWrites))
+}
+
+/// Sets an attribute on the entity of an element with the mutator declared for it, resolving
+/// [`token`](crate::theme::token)s against the theme. Attributes other than `style` and `class`
+/// are set under the element's style, which keeps applying over them.
+pub(crate) fn apply_attribute(
+    world: &mut World,
+    root_entity: Entity,
+    entity: Entity,
+    tag: Option<&'static str>,
+    name: &'static str,
+    value: AttributeValue,
+) -> Result<(), DioxusError> {
+    let error = |kind| {
+        DioxusError::new(root_entity, kind)
+            .with_tag(tag)
+            .with_attribute(name)
+    };
+
+    let (mutator, writes) =
+        find_attribute_mutator(world, tag, name).ok_or(error(DioxusErrorKind::UnknownAttribute))?;
+    let value = crate::theme::resolve_token(world, value).map_err(error)?;
+    match name {
+        "style" | "class" => mutator(world, entity, value),
+        _ => crate::style::set_under_style(world, entity, writes, |world| {
+            mutator(world, entity, value)
+        }),
+    }
+    .map_err(error)
+}
+
+fn spawn_entity(
+    world: &mut World,
+    root_entity: Entity,
+    id: dioxus_core::ElementId,
+) -> Result<Entity, DioxusError> {
+    let entity = world.spawn(Node::default()).id();
+    assign_entity_to_id(world, root_entity, id, entity)?;
+    Ok(entity)
+}
+
+/// Spawns the entity of a text node, as a span until it is inserted somewhere.
+fn spawn_text_fragment(
+    world: &mut World,
+    root_entity: Entity,
+    id: Option<dioxus_core::ElementId>,
+    text: String,
+) -> Result<Entity, DioxusError> {
+    let entity = world.spawn((TextFragment, TextSpan(text))).id();
+    if let Some(id) = id {
+        assign_entity_to_id(world, root_entity, id, entity)?;
+    }
+    Ok(entity)
 }
 
 fn assign_entity_to_id(
@@ -476,21 +520,109 @@ fn assign_entity_to_id(
     root_entity: Entity,
     id: dioxus_core::ElementId,
     entity: Entity,
-) {
-    world
-        .entity_mut(root_entity)
-        .get_mut::<DioxusRoot>()
-        .unwrap()
-        .element_map
-        .insert(id, entity);
+) -> Result<(), DioxusError> {
+    let Some(mut root) = world.get_mut::<DioxusRoot>(root_entity) else {
+        return Err(DioxusError::new(root_entity, DioxusErrorKind::MissingRoot).with_element(id));
+    };
+    root.element_map.insert(id, entity);
+
+    world.entity_mut(entity).insert(DioxusElement {
+        root: root_entity,
+        id,
+    });
+    Ok(())
 }
 
-fn id_to_entity(world: &World, root_entity: Entity, id: dioxus_core::ElementId) -> Entity {
-    *world
-        .entity(root_entity)
-        .get::<DioxusRoot>()
-        .unwrap()
+fn id_to_entity(
+    world: &World,
+    root_entity: Entity,
+    id: dioxus_core::ElementId,
+) -> Result<Entity, DioxusError> {
+    let error = |kind| DioxusError::new(root_entity, kind).with_element(id);
+
+    let root = world
+        .get::<DioxusRoot>(root_entity)
+        .ok_or_else(|| error(DioxusErrorKind::MissingRoot))?;
+    let entity = *root
         .element_map
         .get(&id)
+        .ok_or_else(|| error(DioxusErrorKind::MissingElement))?;
+
+    if world.get_entity(entity).is_err() {
+        return Err(error(DioxusErrorKind::MissingEntity(entity)));
+    }
+    Ok(entity)
+}
+
+/// The parent of the element's entity, and the index of the entity among its children.
+fn position_in_parent(
+    world: &World,
+    root_entity: Entity,
+    id: dioxus_core::ElementId,
+    entity: Entity,
+) -> Result<(Entity, usize), DioxusError> {
+    world
+        .get::<ChildOf>(entity)
+        .and_then(|child_of| {
+            let parent = child_of.parent();
+            let index = world
+                .get::<Children>(parent)?
+                .iter()
+                .position(|c| c == entity)?;
+            Some((parent, index))
+        })
+        .ok_or_else(|| {
+            DioxusError::new(root_entity, DioxusErrorKind::MissingComponent("ChildOf"))
+                .with_element(id)
+        })
+}
+
+/// Takes the last `m` nodes off the stack, in order.
+fn pop_nodes(
+    stack: &Mutex<Vec<Entity>>,
+    root_entity: Entity,
+    m: usize,
+) -> Result<Vec<Entity>, DioxusError> {
+    let mut stack = stack.lock().unwrap();
+    let Some(start) = stack.len().checked_sub(m) else {
+        return Err(DioxusError::new(
+            root_entity,
+            DioxusErrorKind::InvalidMutation("fewer nodes on the stack than the mutation uses"),
+        ));
+    };
+    Ok(stack.drain(start..).collect())
+}
+
+/// Follows `path` through the children of the node on top of the stack.
+fn node_at_path(
+    world: &World,
+    stack: &Mutex<Vec<Entity>>,
+    root_entity: Entity,
+    path: &[u8],
+) -> Result<Entity, DioxusError> {
+    let error = |reason| DioxusError::new(root_entity, DioxusErrorKind::InvalidMutation(reason));
+
+    let mut node = *stack
+        .lock()
         .unwrap()
+        .last()
+        .ok_or_else(|| error("no node on the stack"))?;
+    for index in path {
+        node = world
+            .get::<Children>(node)
+            .and_then(|children| children.get(*index as usize).copied())
+            .ok_or_else(|| error("the node has no child at this path"))?;
+    }
+    Ok(node)
+}
+
+fn despawn(world: &mut World, root_entity: Entity, entity: Entity) -> Result<(), DioxusError> {
+    if world.despawn(entity) {
+        Ok(())
+    } else {
+        Err(DioxusError::new(
+            root_entity,
+            DioxusErrorKind::MissingEntity(entity),
+        ))
+    }
 }
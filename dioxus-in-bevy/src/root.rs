@@ -11,6 +11,12 @@ use dioxus::prelude::*;
 #[cfg(feature = "web")]
 use crate::web_node::Overlay;
 
+#[cfg(not(feature = "web"))]
+#[component]
+fn Overlay() -> Element {
+    VNode::empty()
+}
+
 pub(crate) fn setup_plugin(app: &mut App) {
     app.add_systems(Update, synchronize_components);
 }
@@ -104,10 +110,15 @@ pub fn BevyApp(builder: Option<Box<fn() -> App>>, children: Element) -> Element
 }
 
 fn synchronize_components(
-    root: NonSendMut<DioxusRoot>,
+    root: Option<NonSendMut<DioxusRoot>>,
     added: Query<(Entity, &DioxusNode), Added<DioxusNode>>,
     mut removed: RemovedComponents<DioxusNode>,
 ) {
+    // Only present when the app was mounted by `BevyApp`
+    let Some(root) = root else {
+        return;
+    };
+
     for entity in removed.read() {
         root.components
             .lock()
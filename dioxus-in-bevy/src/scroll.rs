@@ -0,0 +1,206 @@
+//! Scrolling: elements whose `overflow` scrolls, like the library's `scroll`, follow the mouse
+//! wheel and touch drags over them, and report their [`ScrollPosition`] with `onscroll`.
+//!
+//! The innermost hovered element that can still scroll in the direction of the input takes it, so
+//! nested scroll containers hand the input over to their parent once they reach an edge, like on
+//! the web. Holding shift turns the vertical wheel into horizontal scrolling. Disabled elements
+//! neither scroll with the input nor dispatch `onscroll`, so the input goes to their parent.
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::touch::Touches;
+use bevy::picking::hover::HoverMap;
+use bevy::picking::pointer::PointerId;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+use crate::interaction::Disabled;
+use crate::native::{dispatch_event, ElementTag};
+
+/// How far one line of a mouse wheel scrolls, in logical pixels.
+const LINE_HEIGHT: f32 = 20.0;
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    // Headless apps don't add `InputPlugin`, but elements may still scroll programmatically.
+    app.add_event::<MouseWheel>()
+        .add_systems(Update, scroll_with_input)
+        .add_systems(PostUpdate, dispatch_scroll.after(UiSystem::Layout));
+}
+
+/// The payload of `onscroll`: the new offset of the content, along with the size of the viewport
+/// and of the content it scrolls, all in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollData {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub viewport_width: f32,
+    pub viewport_height: f32,
+    pub content_width: f32,
+    pub content_height: f32,
+}
+
+/// Marks an element listening to `onscroll`, with the last offset it was told about.
+#[derive(Component, Default)]
+pub(crate) struct ScrollListener {
+    last_offset: Vec2,
+}
+
+pub(crate) fn add_listener(world: &mut World, entity: Entity, _name: &str) {
+    let mut entity = world.entity_mut(entity);
+    if !entity.contains::<ScrollListener>() {
+        entity.insert(ScrollListener::default());
+    }
+}
+
+pub(crate) fn remove_listener(world: &mut World, entity: Entity, _name: &str) {
+    if let Ok(mut entity) = world.get_entity_mut(entity) {
+        entity.remove::<ScrollListener>();
+    }
+}
+
+type ScrollContainers<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Node,
+        &'static ComputedNode,
+        &'static mut ScrollPosition,
+    ),
+    (With<ElementTag>, Without<Disabled>),
+>;
+
+fn scroll_with_input(
+    mut wheel: EventReader<MouseWheel>,
+    touches: Option<Res<Touches>>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    hover_map: Option<Res<HoverMap>>,
+    parents: Query<&ChildOf>,
+    mut containers: ScrollContainers,
+) {
+    let shift =
+        keys.is_some_and(|keys| keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]));
+
+    // The offset moves against the wheel, and against the finger since the content follows it.
+    let mut inputs = wheel
+        .read()
+        .map(|event| {
+            let mut delta = -Vec2::new(event.x, event.y);
+            if event.unit == MouseScrollUnit::Line {
+                delta *= LINE_HEIGHT;
+            }
+            if shift {
+                delta = delta.yx();
+            }
+            (PointerId::Mouse, delta)
+        })
+        .collect::<Vec<_>>();
+    if let Some(touches) = touches {
+        inputs.extend(
+            touches
+                .iter()
+                .filter(|touch| touch.delta() != Vec2::ZERO)
+                .map(|touch| (PointerId::Touch(touch.id()), -touch.delta())),
+        );
+    }
+
+    let Some(hover_map) = hover_map else {
+        return;
+    };
+    for (pointer, delta) in inputs {
+        let Some(hits) = hover_map.get(&pointer) else {
+            continue;
+        };
+
+        // Innermost hits first, and only the first container that moves takes the input, so a
+        // pointer over nested containers doesn't scroll them all
+        let mut hits = hits.keys().copied().collect::<Vec<_>>();
+        hits.sort_by_cached_key(|&hit| std::cmp::Reverse(parents.iter_ancestors(hit).count()));
+        hits.into_iter()
+            .flat_map(|hit| std::iter::once(hit).chain(parents.iter_ancestors(hit)))
+            .any(|entity| {
+                containers
+                    .get_mut(entity)
+                    .is_ok_and(|(node, computed, mut scroll)| {
+                        scroll_by(node, computed, &mut scroll, delta)
+                    })
+            });
+    }
+}
+
+/// Scrolls a container by `delta`, within its content. Returns whether it moved.
+fn scroll_by(
+    node: &Node,
+    computed: &ComputedNode,
+    scroll: &mut Mut<ScrollPosition>,
+    mut delta: Vec2,
+) -> bool {
+    let scrolls_x = node.overflow.x == OverflowAxis::Scroll;
+    let scrolls_y = node.overflow.y == OverflowAxis::Scroll;
+    // A wheel scrolls containers that only scroll horizontally too.
+    if scrolls_x && !scrolls_y && delta.x == 0.0 {
+        delta = delta.yx();
+    }
+
+    let max = (computed.content_size() - computed.size()).max(Vec2::ZERO)
+        * computed.inverse_scale_factor();
+    let offset = Vec2::new(scroll.offset_x, scroll.offset_y);
+    let mut target = (offset + delta).clamp(Vec2::ZERO, max);
+    if !scrolls_x {
+        target.x = offset.x;
+    }
+    if !scrolls_y {
+        target.y = offset.y;
+    }
+
+    if target == offset {
+        return false;
+    }
+    scroll.offset_x = target.x;
+    scroll.offset_y = target.y;
+    true
+}
+
+fn dispatch_scroll(world: &mut World) {
+    let mut listeners = world.query::<(
+        Entity,
+        &mut ScrollListener,
+        &ScrollPosition,
+        Option<&ComputedNode>,
+        Has<Disabled>,
+    )>();
+    let scrolled = listeners
+        .iter_mut(world)
+        .filter_map(|(entity, mut listener, scroll, computed, disabled)| {
+            let offset = Vec2::new(scroll.offset_x, scroll.offset_y);
+            if listener.last_offset == offset {
+                return None;
+            }
+            // Still followed while disabled, so enabling the element doesn't report a stale scroll
+            listener.last_offset = offset;
+            if disabled {
+                return None;
+            }
+
+            let (viewport, content) = computed
+                .map(|computed| {
+                    let scale = computed.inverse_scale_factor();
+                    (computed.size() * scale, computed.content_size() * scale)
+                })
+                .unwrap_or_default();
+            Some((
+                entity,
+                ScrollData {
+                    offset_x: offset.x,
+                    offset_y: offset.y,
+                    viewport_width: viewport.x,
+                    viewport_height: viewport.y,
+                    content_width: content.x,
+                    content_height: content.y,
+                },
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, data) in scrolled {
+        dispatch_event(world, entity, "scroll", data, false);
+    }
+}
@@ -0,0 +1,610 @@
+//! The `style` attribute, which sets the layout and styling components of an element with CSS
+//! declarations, like `style: "display: flex; width: 50%; padding: 8px 16px"`.
+//!
+//! Properties use their CSS names and the value syntax of [`value`](crate::value), and map onto
+//! the fields of [`Node`], [`BackgroundColor`], [`BorderColor`], [`BorderRadius`], [`Outline`],
+//! [`ZIndex`] and [`BoxShadow`]. Shorthands like `border`, `flex`, `gap` or `inset` expand to the
+//! properties they stand for. A style with an unsupported property or an invalid value is reported
+//! as a [`DioxusError`](crate::error::DioxusError), and none of it is applied.
+//!
+//! When the style changes, only the properties it declares are set, and the properties it stopped
+//! declaring are reset, so formatted styles like `style: "width: {width}px"` can be diffed by
+//! Dioxus like any other attribute. A reset property gets back the value the element's other
+//! attributes set for it, like `width: Val::Px(100.0)`, kept in its [`AttributeStyle`] while the
+//! style overrides it, or its value in the element's bundle when no attribute set one.
+
+use std::any::TypeId;
+
+use bevy::prelude::*;
+use bevy::ui::{
+    AlignContent, AlignItems, AlignSelf, BoxSizing, Display, FlexDirection, FlexWrap, GridAutoFlow,
+    GridPlacement, GridTrack, JustifyContent, JustifyItems, JustifySelf, OverflowAxis,
+    OverflowClipBox, OverflowClipMargin, PositionType, RepeatedGridTrack, ShadowStyle,
+};
+use dioxus_core::AttributeValue;
+
+use crate::component::{convert_attribute, AttributeWrites, ElementDefaults};
+use crate::error::DioxusErrorKind;
+use crate::native::{DioxusBuilders, ElementTag};
+use crate::stylesheet::MatchedStyle;
+use crate::value::{
+    parse_border_radius, parse_box_shadow, parse_color, parse_enum, parse_grid_placement,
+    parse_grid_tracks, parse_overflow, parse_repeated_grid_tracks, parse_text, parse_ui_rect,
+    parse_val,
+};
+
+/// The properties set by the `style` attribute of an element, in the order they were declared.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct InlineStyle(pub Vec<StyleProperty>);
+
+/// The values the other attributes of an element set for the properties its style or the
+/// stylesheet rules matching it override, so they can be restored once neither sets them.
+///
+/// Attributes set while a property is overridden update the value kept here, rather than the
+/// styled component.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct AttributeStyle(pub Vec<StyleProperty>);
+
+macro_rules! style_properties {
+    ($($name:literal => $variant:ident($type:ty): $component:ident $(. $field:tt)+ = $parse:expr;)*) => {
+        /// A CSS property the `style` attribute supports, with its value.
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum StyleProperty {
+            $($variant($type),)*
+        }
+
+        impl StyleProperty {
+            /// The CSS name of the property.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(_) => $name,)*
+                }
+            }
+
+            /// The [`TypeId`] of the component the property belongs to.
+            fn component(&self) -> TypeId {
+                match self {
+                    $(Self::$variant(_) => TypeId::of::<$component>(),)*
+                }
+            }
+
+            /// Parses the value of the property called `name`, if there is one.
+            fn parse(name: &str, value: &str) -> Option<Result<Self, DioxusErrorKind>> {
+                match name {
+                    $($name => Some(($parse)(value).map(Self::$variant)),)*
+                    _ => None,
+                }
+            }
+
+            /// Sets the property on `entity`, inserting the component it belongs to if the entity
+            /// doesn't have one yet. Components are only changed when the value differs.
+            pub fn apply(self, entity: &mut EntityWorldMut) {
+                match self {
+                    $(
+                        Self::$variant(value) => {
+                            if entity.get::<$component>().is_none() {
+                                entity.insert(<$component>::default());
+                            }
+                            if entity.get::<$component>().is_some_and(|component| component$(.$field)+ != value) {
+                                if let Some(mut component) = entity.get_mut::<$component>() {
+                                    component$(.$field)+ = value;
+                                }
+                            }
+                        }
+                    )*
+                }
+            }
+
+            /// The same property, with its value on `defaults`, or its default value if
+            /// `defaults` doesn't have the component.
+            fn default_from(&self, defaults: Option<EntityRef>) -> Self {
+                match self {
+                    $(
+                        Self::$variant(_) => Self::$variant(
+                            defaults
+                                .and_then(|defaults| defaults.get::<$component>().cloned())
+                                .unwrap_or_default()
+                                $(.$field)+,
+                        ),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+style_properties! {
+    "display" => Display(Display): Node.display = parse_enum;
+    "box-sizing" => BoxSizing(BoxSizing): Node.box_sizing = parse_enum;
+    "position" => Position(PositionType): Node.position_type = parse_enum;
+    "overflow-x" => OverflowX(OverflowAxis): Node.overflow.x = parse_enum;
+    "overflow-y" => OverflowY(OverflowAxis): Node.overflow.y = parse_enum;
+    "overflow-clip-margin" => OverflowClipMargin(OverflowClipMargin): Node.overflow_clip_margin = parse_overflow_clip_margin;
+    "left" => Left(Val): Node.left = parse_val;
+    "right" => Right(Val): Node.right = parse_val;
+    "top" => Top(Val): Node.top = parse_val;
+    "bottom" => Bottom(Val): Node.bottom = parse_val;
+    "width" => Width(Val): Node.width = parse_val;
+    "height" => Height(Val): Node.height = parse_val;
+    "min-width" => MinWidth(Val): Node.min_width = parse_val;
+    "min-height" => MinHeight(Val): Node.min_height = parse_val;
+    "max-width" => MaxWidth(Val): Node.max_width = parse_val;
+    "max-height" => MaxHeight(Val): Node.max_height = parse_val;
+    "aspect-ratio" => AspectRatio(Option<f32>): Node.aspect_ratio = parse_aspect_ratio;
+    "align-items" => AlignItems(AlignItems): Node.align_items = parse_enum;
+    "justify-items" => JustifyItems(JustifyItems): Node.justify_items = parse_enum;
+    "align-self" => AlignSelf(AlignSelf): Node.align_self = parse_enum;
+    "justify-self" => JustifySelf(JustifySelf): Node.justify_self = parse_enum;
+    "align-content" => AlignContent(AlignContent): Node.align_content = parse_enum;
+    "justify-content" => JustifyContent(JustifyContent): Node.justify_content = parse_enum;
+    "margin" => Margin(UiRect): Node.margin = parse_ui_rect;
+    "margin-top" => MarginTop(Val): Node.margin.top = parse_val;
+    "margin-right" => MarginRight(Val): Node.margin.right = parse_val;
+    "margin-bottom" => MarginBottom(Val): Node.margin.bottom = parse_val;
+    "margin-left" => MarginLeft(Val): Node.margin.left = parse_val;
+    "padding" => Padding(UiRect): Node.padding = parse_ui_rect;
+    "padding-top" => PaddingTop(Val): Node.padding.top = parse_val;
+    "padding-right" => PaddingRight(Val): Node.padding.right = parse_val;
+    "padding-bottom" => PaddingBottom(Val): Node.padding.bottom = parse_val;
+    "padding-left" => PaddingLeft(Val): Node.padding.left = parse_val;
+    "border-width" => BorderWidth(UiRect): Node.border = parse_ui_rect;
+    "border-top-width" => BorderTopWidth(Val): Node.border.top = parse_val;
+    "border-right-width" => BorderRightWidth(Val): Node.border.right = parse_val;
+    "border-bottom-width" => BorderBottomWidth(Val): Node.border.bottom = parse_val;
+    "border-left-width" => BorderLeftWidth(Val): Node.border.left = parse_val;
+    "flex-direction" => FlexDirection(FlexDirection): Node.flex_direction = parse_enum;
+    "flex-wrap" => FlexWrap(FlexWrap): Node.flex_wrap = parse_enum;
+    "flex-grow" => FlexGrow(f32): Node.flex_grow = parse_text;
+    "flex-shrink" => FlexShrink(f32): Node.flex_shrink = parse_text;
+    "flex-basis" => FlexBasis(Val): Node.flex_basis = parse_val;
+    "row-gap" => RowGap(Val): Node.row_gap = parse_val;
+    "column-gap" => ColumnGap(Val): Node.column_gap = parse_val;
+    "grid-auto-flow" => GridAutoFlow(GridAutoFlow): Node.grid_auto_flow = parse_enum;
+    "grid-template-rows" => GridTemplateRows(Vec<RepeatedGridTrack>): Node.grid_template_rows = parse_repeated_grid_tracks;
+    "grid-template-columns" => GridTemplateColumns(Vec<RepeatedGridTrack>): Node.grid_template_columns = parse_repeated_grid_tracks;
+    "grid-auto-rows" => GridAutoRows(Vec<GridTrack>): Node.grid_auto_rows = parse_grid_tracks;
+    "grid-auto-columns" => GridAutoColumns(Vec<GridTrack>): Node.grid_auto_columns = parse_grid_tracks;
+    "grid-row" => GridRow(GridPlacement): Node.grid_row = parse_grid_placement;
+    "grid-column" => GridColumn(GridPlacement): Node.grid_column = parse_grid_placement;
+    "background-color" => BackgroundColor(Color): BackgroundColor.0 = parse_color;
+    "border-color" => BorderColor(Color): BorderColor.0 = parse_color;
+    "border-top-left-radius" => BorderTopLeftRadius(Val): BorderRadius.top_left = parse_val;
+    "border-top-right-radius" => BorderTopRightRadius(Val): BorderRadius.top_right = parse_val;
+    "border-bottom-right-radius" => BorderBottomRightRadius(Val): BorderRadius.bottom_right = parse_val;
+    "border-bottom-left-radius" => BorderBottomLeftRadius(Val): BorderRadius.bottom_left = parse_val;
+    "outline-width" => OutlineWidth(Val): Outline.width = parse_val;
+    "outline-offset" => OutlineOffset(Val): Outline.offset = parse_val;
+    "outline-color" => OutlineColor(Color): Outline.color = parse_color;
+    "z-index" => ZIndex(i32): ZIndex.0 = parse_text;
+    "box-shadow" => BoxShadow(Vec<ShadowStyle>): BoxShadow.0 = |text| parse_box_shadow(text).map(|shadow| shadow.0);
+}
+
+/// Parses `auto`, a ratio like `1.5`, or a width and a height like `16 / 9`.
+fn parse_aspect_ratio(text: &str) -> Result<Option<f32>, DioxusErrorKind> {
+    let trimmed = text.trim();
+    if trimmed.eq_ignore_ascii_case("auto") {
+        return Ok(None);
+    }
+    match trimmed.split_once('/') {
+        Some((width, height)) => Ok(Some(parse_text::<f32>(width)? / parse_text::<f32>(height)?)),
+        None => parse_text(trimmed).map(Some),
+    }
+}
+
+/// Parses a visual box, a margin in pixels, or both, like `padding-box 4px`.
+fn parse_overflow_clip_margin(text: &str) -> Result<OverflowClipMargin, DioxusErrorKind> {
+    let mut margin = OverflowClipMargin::DEFAULT;
+    for part in text.split_whitespace() {
+        match parse_val(part) {
+            Ok(Val::Px(pixels)) => margin.margin = pixels,
+            Ok(_) => {
+                return Err(DioxusErrorKind::InvalidValue(format!(
+                    "`{part}` is not a length in pixels"
+                )))
+            }
+            Err(_) => margin.visual_box = parse_enum::<OverflowClipBox>(part)?,
+        }
+    }
+    Ok(margin)
+}
+
+/// Parses the widths and color of the `border` and `outline` shorthands, like `1px solid red`.
+/// Line styles other than `none` are accepted but ignored, since Bevy only draws solid lines.
+fn parse_line(text: &str) -> Result<(Option<Val>, Option<Color>), DioxusErrorKind> {
+    let mut width = None;
+    let mut color = None;
+    for part in text.split_whitespace() {
+        match part.to_ascii_lowercase().as_str() {
+            "none" => width = Some(Val::ZERO),
+            "solid" | "dashed" | "dotted" | "double" | "groove" | "ridge" | "inset" | "outset" => {}
+            _ => match parse_val(part) {
+                Ok(value) => width = Some(value),
+                Err(_) => color = Some(parse_color(part)?),
+            },
+        }
+    }
+    Ok((width, color))
+}
+
+/// Expands a shorthand property into the properties it sets, if `name` is one.
+fn parse_shorthand(name: &str, value: &str) -> Result<Option<Vec<StyleProperty>>, DioxusErrorKind> {
+    use StyleProperty::*;
+
+    Ok(Some(match name {
+        "background" => vec![BackgroundColor(parse_color(value)?)],
+        "overflow" => {
+            let overflow = parse_overflow(value)?;
+            vec![OverflowX(overflow.x), OverflowY(overflow.y)]
+        }
+        "inset" => {
+            let inset = parse_ui_rect(value)?;
+            vec![
+                Top(inset.top),
+                Right(inset.right),
+                Bottom(inset.bottom),
+                Left(inset.left),
+            ]
+        }
+        "gap" => match value.split_whitespace().collect::<Vec<_>>()[..] {
+            [both] => vec![RowGap(parse_val(both)?), ColumnGap(parse_val(both)?)],
+            [row, column] => vec![RowGap(parse_val(row)?), ColumnGap(parse_val(column)?)],
+            _ => return Err(invalid_declaration(name, value)),
+        },
+        "flex" => {
+            let (grow, shrink, basis) = match value.trim().to_ascii_lowercase().as_str() {
+                "none" => (0.0, 0.0, Val::Auto),
+                "auto" => (1.0, 1.0, Val::Auto),
+                _ => match value.split_whitespace().collect::<Vec<_>>()[..] {
+                    [grow] => match parse_text::<f32>(grow) {
+                        Ok(grow) => (grow, 1.0, Val::Percent(0.0)),
+                        Err(_) => (1.0, 1.0, parse_val(grow)?),
+                    },
+                    [grow, second] => match parse_text::<f32>(second) {
+                        Ok(shrink) => (parse_text(grow)?, shrink, Val::Percent(0.0)),
+                        Err(_) => (parse_text(grow)?, 1.0, parse_val(second)?),
+                    },
+                    [grow, shrink, basis] => {
+                        (parse_text(grow)?, parse_text(shrink)?, parse_val(basis)?)
+                    }
+                    _ => return Err(invalid_declaration(name, value)),
+                },
+            };
+            vec![FlexGrow(grow), FlexShrink(shrink), FlexBasis(basis)]
+        }
+        "border" => {
+            let (width, color) = parse_line(value)?;
+            width
+                .map(|width| BorderWidth(UiRect::all(width)))
+                .into_iter()
+                .chain(color.map(BorderColor))
+                .collect()
+        }
+        "outline" => {
+            let (width, color) = parse_line(value)?;
+            width
+                .map(OutlineWidth)
+                .into_iter()
+                .chain(color.map(OutlineColor))
+                .collect()
+        }
+        "border-radius" => {
+            let radius = parse_border_radius(value)?;
+            vec![
+                BorderTopLeftRadius(radius.top_left),
+                BorderTopRightRadius(radius.top_right),
+                BorderBottomRightRadius(radius.bottom_right),
+                BorderBottomLeftRadius(radius.bottom_left),
+            ]
+        }
+        _ => return Ok(None),
+    }))
+}
+
+fn invalid_declaration(name: &str, value: &str) -> DioxusErrorKind {
+    DioxusErrorKind::InvalidValue(format!("`{value}` is not a valid value for `{name}`"))
+}
+
+/// Parses CSS declarations separated by `;`, expanding shorthands into the properties they set.
+/// Property names ignore case, and may use `_` instead of `-`.
+pub fn parse_style(text: &str) -> Result<Vec<StyleProperty>, DioxusErrorKind> {
+    let mut properties = Vec::new();
+
+    for declaration in text.split(';').map(str::trim).filter(|d| !d.is_empty()) {
+        let Some((name, value)) = declaration.split_once(':') else {
+            return Err(DioxusErrorKind::InvalidValue(format!(
+                "`{declaration}` is not a `property: value` declaration"
+            )));
+        };
+        let name = name.trim().to_ascii_lowercase().replace('_', "-");
+        let value = value.trim();
+
+        let parsed = match StyleProperty::parse(&name, value) {
+            Some(property) => property.map(|property| vec![property]),
+            None => parse_shorthand(&name, value).and_then(|properties| {
+                properties.ok_or_else(|| DioxusErrorKind::UnsupportedStyleProperty(name.clone()))
+            }),
+        };
+        properties.extend(parsed.map_err(|error| match error {
+            DioxusErrorKind::InvalidValue(reason) => {
+                DioxusErrorKind::InvalidValue(format!("{reason} (in `{name}`)"))
+            }
+            error => error,
+        })?);
+    }
+
+    Ok(properties)
+}
+
+pub(crate) fn set_style(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    let properties = match value {
+        AttributeValue::None => Vec::new(),
+        value => parse_style(&convert_attribute::<String>(value)?)?,
+    };
+
+    let previous = world
+        .entity_mut(entity)
+        .take::<InlineStyle>()
+        .unwrap_or_default();
+    let removed = previous
+        .0
+        .into_iter()
+        .filter(|old| !properties.iter().any(|new| new.name() == old.name()))
+        .collect::<Vec<_>>();
+    reset_properties(world, entity, &removed);
+
+    override_properties(world, entity, &properties);
+    if !properties.is_empty() {
+        world.entity_mut(entity).insert(InlineStyle(properties));
+    }
+
+    Ok(())
+}
+
+/// Applies `properties` over the values the attributes of `entity` set, keeping those in its
+/// [`AttributeStyle`] the first time a property is overridden.
+pub(crate) fn override_properties(world: &mut World, entity: Entity, properties: &[StyleProperty]) {
+    if properties.is_empty() {
+        return;
+    }
+
+    let mut entity = world.entity_mut(entity);
+    let mut kept = entity.take::<AttributeStyle>().unwrap_or_default();
+    for property in properties {
+        if !kept.0.iter().any(|p| p.name() == property.name()) {
+            kept.0
+                .push(property.default_from(Some(entity.as_readonly())));
+        }
+        property.clone().apply(&mut entity);
+    }
+    entity.insert(kept);
+}
+
+/// Resets `properties` on `entity` to the values its attributes set, kept in its
+/// [`AttributeStyle`], or to their value in a fresh bundle of its element when no attribute set
+/// one, like removed attributes.
+pub(crate) fn reset_properties(world: &mut World, entity: Entity, properties: &[StyleProperty]) {
+    if properties.is_empty() {
+        return;
+    }
+
+    let mut kept = world
+        .entity_mut(entity)
+        .take::<AttributeStyle>()
+        .unwrap_or_default();
+    let mut missing = Vec::<&StyleProperty>::new();
+    let mut reset = Vec::new();
+    for property in properties {
+        // Properties declared more than once are only reset once, to the value kept the first time
+        if reset
+            .iter()
+            .chain(missing.iter().copied())
+            .any(|p| p.name() == property.name())
+        {
+            continue;
+        }
+        match kept.0.iter().position(|p| p.name() == property.name()) {
+            Some(index) => reset.push(kept.0.remove(index)),
+            None => missing.push(property),
+        }
+    }
+
+    if !missing.is_empty() {
+        let builder = world
+            .get::<ElementTag>(entity)
+            .and_then(|tag| world.resource::<DioxusBuilders>().get(tag.0).copied());
+        let mut defaults = world.resource_mut::<ElementDefaults>();
+        let defaults = builder.map(|builder| defaults.get(builder));
+        reset.extend(
+            missing
+                .into_iter()
+                .map(|property| property.default_from(defaults)),
+        );
+    }
+
+    let mut entity = world.entity_mut(entity);
+    for property in reset {
+        property.apply(&mut entity);
+    }
+    if !kept.0.is_empty() {
+        entity.insert(kept);
+    }
+}
+
+/// Runs `mutate`, which sets an attribute other than `style` or `class` on `entity`, under its
+/// style: the overridden properties of the components the attribute `writes` get back the values
+/// attributes set for them first, so `mutate` updates those, which are kept again before the style
+/// is applied over them.
+pub(crate) fn set_under_style<R>(
+    world: &mut World,
+    entity: Entity,
+    writes: AttributeWrites,
+    mutate: impl FnOnce(&mut World) -> R,
+) -> R {
+    let written = world
+        .get::<AttributeStyle>(entity)
+        .map(|kept| {
+            kept.0
+                .iter()
+                .filter(|property| writes(property.component()))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if written.is_empty() {
+        return mutate(world);
+    }
+
+    let mut target = world.entity_mut(entity);
+    for property in &written {
+        property.clone().apply(&mut target);
+    }
+
+    let result = mutate(world);
+
+    let Ok(mut target) = world.get_entity_mut(entity) else {
+        return result;
+    };
+    let changed = written
+        .iter()
+        .map(|property| property.default_from(Some(target.as_readonly())))
+        .filter(|property| !written.contains(property))
+        .collect::<Vec<_>>();
+    if !changed.is_empty() {
+        if let Some(mut kept) = target.get_mut::<AttributeStyle>() {
+            for property in changed {
+                if let Some(old) = kept.0.iter_mut().find(|p| p.name() == property.name()) {
+                    *old = property;
+                }
+            }
+        }
+    }
+    for property in overriding_properties(&target) {
+        if writes(property.component()) {
+            property.apply(&mut target);
+        }
+    }
+
+    result
+}
+
+/// The properties applied over the attributes of `entity`, in the order they apply: those of the
+/// stylesheet rules matching it, then those of its `style` attribute.
+fn overriding_properties(entity: &EntityWorldMut) -> Vec<StyleProperty> {
+    let matched = entity.get::<MatchedStyle>().map(|matched| &matched.0);
+    let inline = entity.get::<InlineStyle>().map(|inline| &inline.0);
+    matched
+        .into_iter()
+        .chain(inline)
+        .flatten()
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::color::Color;
+    use bevy::ui::{OverflowAxis, UiRect, Val};
+
+    use super::parse_style;
+    use super::StyleProperty::*;
+    use crate::error::DioxusErrorKind;
+
+    #[test]
+    fn parses_declarations_in_order() {
+        assert_eq!(
+            parse_style(" width: 50% ; Background_Color: red;; z-index: 2 "),
+            Ok(vec![
+                Width(Val::Percent(50.0)),
+                BackgroundColor(Color::srgb(1.0, 0.0, 0.0)),
+                ZIndex(2),
+            ])
+        );
+        assert_eq!(parse_style(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn expands_shorthands() {
+        let px = Val::Px;
+        assert_eq!(
+            parse_style("overflow: hidden scroll"),
+            Ok(vec![
+                OverflowX(OverflowAxis::Hidden),
+                OverflowY(OverflowAxis::Scroll)
+            ])
+        );
+        assert_eq!(
+            parse_style("inset: 1px 2px"),
+            Ok(vec![
+                Top(px(1.0)),
+                Right(px(2.0)),
+                Bottom(px(1.0)),
+                Left(px(2.0))
+            ])
+        );
+        assert_eq!(
+            parse_style("gap: 4px"),
+            Ok(vec![RowGap(px(4.0)), ColumnGap(px(4.0))])
+        );
+        assert_eq!(
+            parse_style("border: 2px solid blue"),
+            Ok(vec![
+                BorderWidth(UiRect::all(px(2.0))),
+                BorderColor(Color::srgb(0.0, 0.0, 1.0)),
+            ])
+        );
+        assert_eq!(
+            parse_style("outline: none"),
+            Ok(vec![OutlineWidth(Val::ZERO)])
+        );
+        assert_eq!(
+            parse_style("border-radius: 1px 2px"),
+            Ok(vec![
+                BorderTopLeftRadius(px(1.0)),
+                BorderTopRightRadius(px(2.0)),
+                BorderBottomRightRadius(px(1.0)),
+                BorderBottomLeftRadius(px(2.0)),
+            ])
+        );
+    }
+
+    #[test]
+    fn expands_flex_like_css() {
+        let flex = |value| match parse_style(&format!("flex: {value}")).as_deref() {
+            Ok([FlexGrow(grow), FlexShrink(shrink), FlexBasis(basis)]) => (*grow, *shrink, *basis),
+            parsed => panic!("`flex: {value}` parsed as {parsed:?}"),
+        };
+        assert_eq!(flex("none"), (0.0, 0.0, Val::Auto));
+        assert_eq!(flex("auto"), (1.0, 1.0, Val::Auto));
+        assert_eq!(flex("2"), (2.0, 1.0, Val::Percent(0.0)));
+        assert_eq!(flex("100px"), (1.0, 1.0, Val::Px(100.0)));
+        assert_eq!(flex("2 3"), (2.0, 3.0, Val::Percent(0.0)));
+        assert_eq!(flex("2 50%"), (2.0, 1.0, Val::Percent(50.0)));
+        assert_eq!(flex("2 3 10px"), (2.0, 3.0, Val::Px(10.0)));
+        assert!(parse_style("flex: 1 2 3 4").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_declarations() {
+        assert!(matches!(
+            parse_style("width"),
+            Err(DioxusErrorKind::InvalidValue(_))
+        ));
+        assert_eq!(
+            parse_style("width: 1px; float: left"),
+            Err(DioxusErrorKind::UnsupportedStyleProperty(
+                "float".to_string()
+            ))
+        );
+        let Err(DioxusErrorKind::InvalidValue(reason)) = parse_style("gap: 1px 2px 3px") else {
+            panic!("a gap of three lengths is invalid");
+        };
+        assert!(reason.ends_with("(in `gap`)"), "{reason}");
+        assert!(parse_style("width: wide").is_err());
+        assert!(parse_style("border: 1px thick").is_err());
+        assert!(parse_style("aspect-ratio: 16 /").is_err());
+    }
+}
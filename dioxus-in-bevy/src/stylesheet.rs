@@ -0,0 +1,725 @@
+//! Stylesheets: `.bss` assets of CSS rules applied to the elements of a root by their tag, their
+//! `class` attribute and their state, like
+//!
+//! ```css
+//! /* ui.bss */
+//! .button { padding: 8px 16px; background: #303030; border-radius: 4px }
+//! .button:hover { background: #404040 }
+//! .menu > .item, .title { margin-bottom: 8px }
+//! ```
+//!
+//! A selector is made of compounds of an element tag (or `*`), any number of `.class`es and the
+//! `:hover`, `:active` (or `:pressed`) and `:disabled` pseudo-classes, separated by the descendant
+//! (whitespace) and child (`>`) combinators. Declarations use the syntax of the
+//! [`style`](crate::style) attribute.
+//!
+//! Rules apply in order of specificity, then in the order they were declared. They apply over the
+//! other attributes of an element, like `width: Val::Px(100.0)`, and the `style` attribute applies
+//! over all of them. Elements are restyled directly on their entities when their classes or state
+//! change and when the asset is reloaded, so neither re-renders any component.
+//!
+//! Like with the `style` attribute, an attribute set while a rule overrides its property is kept in
+//! the element's [`AttributeStyle`](crate::style::AttributeStyle), and properties no rule sets
+//! anymore get back the value attributes set for them, or their value in the element's bundle
+//! when no attribute did. So `node { width: Val::Px(100.0), class: "big" }` styled by
+//! `.big:hover { width: 200px }` goes back to `100px` once it isn't hovered anymore.
+//!
+//! Stylesheets are assets, so they are only available when the `AssetPlugin` is added, as it is by
+//! `DefaultPlugins`.
+
+use std::collections::HashSet;
+use std::fmt::{self, Display};
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::picking::hover::PickingInteraction;
+use bevy::prelude::*;
+use dioxus_core::AttributeValue;
+
+use crate::component::convert_attribute;
+use crate::error::DioxusErrorKind;
+use crate::interaction::{pseudo_state, Disabled, PseudoState};
+use crate::native::{DioxusRoot, ElementTag};
+use crate::style::{
+    override_properties, parse_style, reset_properties, InlineStyle, StyleProperty,
+};
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    if !app.world().contains_resource::<AssetServer>() {
+        return;
+    }
+
+    app.init_asset::<StyleSheet>()
+        .init_asset_loader::<StyleSheetLoader>()
+        .init_resource::<PendingRestyles>()
+        .add_systems(
+            Update,
+            (collect_restyles, restyle)
+                .chain()
+                .after(crate::native::process_commands),
+        );
+}
+
+/// The classes of an element, set by its `class` attribute as a whitespace separated list.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct Classes(pub Vec<String>);
+
+impl Classes {
+    pub fn contains(&self, class: &str) -> bool {
+        self.0.iter().any(|c| c == class)
+    }
+}
+
+pub(crate) fn set_class(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    let classes = match value {
+        AttributeValue::None => Vec::new(),
+        value => convert_attribute::<String>(value)?
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+    };
+
+    if classes.is_empty() {
+        world.entity_mut(entity).remove::<Classes>();
+    } else {
+        world.entity_mut(entity).insert(Classes(classes));
+    }
+
+    Ok(())
+}
+
+/// The properties the stylesheets of its root set on an element, in the order they were applied.
+#[derive(Component, Debug, Clone, PartialEq, Default)]
+pub struct MatchedStyle(pub Vec<StyleProperty>);
+
+/// A stylesheet, added to a root with
+/// [`DioxusRoot::with_stylesheet`](crate::native::DioxusRoot::with_stylesheet).
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Default)]
+pub struct StyleSheet {
+    pub rules: Vec<StyleRule>,
+}
+
+/// Properties set on the elements matching any of the selectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleRule {
+    pub selectors: Vec<Selector>,
+    pub properties: Vec<StyleProperty>,
+}
+
+impl StyleSheet {
+    /// Parses the rules of a stylesheet, like the [`StyleSheetLoader`] does for `.bss` files.
+    pub fn parse(text: &str) -> Result<Self, StyleSheetError> {
+        let source = strip_comments(text)?;
+        let line = |offset: usize| source[..offset].matches('\n').count() + 1;
+
+        let mut rules = Vec::new();
+        let mut offset = 0;
+        while let Some(start) = source[offset..]
+            .find(|c: char| !c.is_whitespace())
+            .map(|start| offset + start)
+        {
+            let error = |reason: String| StyleSheetError::Parse {
+                line: line(start),
+                reason,
+            };
+
+            let open = source[start..]
+                .find(['{', '}'])
+                .filter(|open| source[start + open..].starts_with('{'))
+                .map(|open| start + open)
+                .ok_or_else(|| error("expected `{` after the selector".to_string()))?;
+            let close = source[open + 1..]
+                .find(['{', '}'])
+                .filter(|close| source[open + 1 + close..].starts_with('}'))
+                .map(|close| open + 1 + close)
+                .ok_or_else(|| error("expected `}` at the end of the rule".to_string()))?;
+
+            let selectors = source[start..open]
+                .split(',')
+                .map(Selector::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(error)?;
+            let properties =
+                parse_style(&source[open + 1..close]).map_err(|kind| StyleSheetError::Parse {
+                    line: line(open),
+                    reason: kind.to_string(),
+                })?;
+            rules.push(StyleRule {
+                selectors,
+                properties,
+            });
+
+            offset = close + 1;
+        }
+
+        Ok(Self { rules })
+    }
+}
+
+/// Blanks out `/* */` comments, keeping their line breaks so errors report the right line.
+fn strip_comments(text: &str) -> Result<String, StyleSheetError> {
+    let mut source = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("/*") {
+        source.push_str(&rest[..start]);
+        let Some(end) = rest[start + 2..].find("*/") else {
+            return Err(StyleSheetError::Parse {
+                line: source.matches('\n').count() + 1,
+                reason: "unterminated comment".to_string(),
+            });
+        };
+        let comment = &rest[start..start + 2 + end + 2];
+        source.extend(comment.chars().map(|c| if c == '\n' { '\n' } else { ' ' }));
+        rest = &rest[start + comment.len()..];
+    }
+    source.push_str(rest);
+    Ok(source)
+}
+
+/// A selector like `.menu > .item:hover`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    compounds: Vec<Compound>,
+    /// The combinator between each compound and the next one.
+    combinators: Vec<Combinator>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Compound {
+    tag: Option<String>,
+    classes: Vec<String>,
+    states: Vec<PseudoClass>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PseudoClass {
+    Hover,
+    Active,
+    Disabled,
+}
+
+impl Selector {
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut compounds = Vec::new();
+        let mut combinators = Vec::new();
+        let mut pending = None;
+
+        let mut rest = text.trim();
+        while !rest.is_empty() {
+            if rest.starts_with(char::is_whitespace) {
+                rest = rest.trim_start();
+                pending = pending.or(Some(Combinator::Descendant));
+            } else if let Some(after) = rest.strip_prefix('>') {
+                if compounds.is_empty() || pending == Some(Combinator::Child) {
+                    return Err(format!("misplaced `>` in `{}`", text.trim()));
+                }
+                rest = after;
+                pending = Some(Combinator::Child);
+            } else {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                if let Some(combinator) = pending.take() {
+                    combinators.push(combinator);
+                }
+                compounds.push(Compound::parse(&rest[..end])?);
+                rest = &rest[end..];
+            }
+        }
+
+        if compounds.is_empty() {
+            return Err("expected a selector".to_string());
+        }
+        if pending == Some(Combinator::Child) {
+            return Err(format!("misplaced `>` in `{}`", text.trim()));
+        }
+
+        Ok(Self {
+            compounds,
+            combinators,
+        })
+    }
+
+    /// The number of classes and pseudo-classes, then of tags, in the selector. Rules with a
+    /// higher specificity apply over the others.
+    pub fn specificity(&self) -> (usize, usize) {
+        self.compounds.iter().fold((0, 0), |(classes, tags), c| {
+            (
+                classes + c.classes.len() + c.states.len(),
+                tags + usize::from(c.tag.is_some()),
+            )
+        })
+    }
+
+    /// Whether the element rendered to `entity` matches the selector.
+    pub fn matches(&self, world: &World, entity: Entity) -> bool {
+        self.matches_from(world, entity, self.compounds.len() - 1)
+    }
+
+    fn matches_from(&self, world: &World, entity: Entity, index: usize) -> bool {
+        if !self.compounds[index].matches(world, entity, true) {
+            return false;
+        }
+        if index == 0 {
+            return true;
+        }
+
+        let parent = |entity| world.get::<ChildOf>(entity).map(ChildOf::parent);
+        match self.combinators[index - 1] {
+            Combinator::Child => {
+                parent(entity).is_some_and(|parent| self.matches_from(world, parent, index - 1))
+            }
+            Combinator::Descendant => std::iter::successors(parent(entity), |&e| parent(e))
+                .any(|ancestor| self.matches_from(world, ancestor, index - 1)),
+        }
+    }
+}
+
+impl Compound {
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut compound = Compound::default();
+
+        let end = text.find(['.', ':']).unwrap_or(text.len());
+        match &text[..end] {
+            "" | "*" => {}
+            tag => compound.tag = Some(parse_identifier(tag)?.to_string()),
+        }
+
+        let mut rest = &text[end..];
+        while let Some(kind) = rest.chars().next() {
+            let end = rest[1..].find(['.', ':']).map_or(rest.len(), |end| end + 1);
+            let name = parse_identifier(&rest[1..end])?;
+            match kind {
+                '.' => compound.classes.push(name.to_string()),
+                _ => compound.states.push(match name {
+                    "hover" => PseudoClass::Hover,
+                    "active" | "pressed" => PseudoClass::Active,
+                    "disabled" => PseudoClass::Disabled,
+                    _ => return Err(format!("unsupported pseudo-class `:{name}`")),
+                }),
+            }
+            rest = &rest[end..];
+        }
+
+        Ok(compound)
+    }
+
+    /// Whether `entity` is an element matching the compound, ignoring its pseudo-classes unless
+    /// `states` is set.
+    fn matches(&self, world: &World, entity: Entity, states: bool) -> bool {
+        let Ok(entity) = world.get_entity(entity) else {
+            return false;
+        };
+        let Some(tag) = entity.get::<ElementTag>() else {
+            return false;
+        };
+
+        if self.tag.as_ref().is_some_and(|t| t != tag.0) {
+            return false;
+        }
+        if !self.classes.is_empty() {
+            let Some(classes) = entity.get::<Classes>() else {
+                return false;
+            };
+            if !self.classes.iter().all(|class| classes.contains(class)) {
+                return false;
+            }
+        }
+        if states && !self.states.is_empty() {
+            let state = pseudo_state(
+                entity.get::<Interaction>(),
+                entity.get::<PickingInteraction>(),
+                entity.contains::<Disabled>(),
+            );
+            return self.states.iter().all(|pseudo| match pseudo {
+                PseudoClass::Hover => {
+                    matches!(state, Some(PseudoState::Hovered | PseudoState::Pressed))
+                }
+                PseudoClass::Active => state == Some(PseudoState::Pressed),
+                PseudoClass::Disabled => state == Some(PseudoState::Disabled),
+            });
+        }
+
+        true
+    }
+}
+
+fn parse_identifier(text: &str) -> Result<&str, String> {
+    if !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(text)
+    } else {
+        Err(format!("`{text}` is not a valid name in a selector"))
+    }
+}
+
+/// Loads [`StyleSheet`]s from `.bss` files.
+#[derive(Default)]
+pub struct StyleSheetLoader;
+
+impl AssetLoader for StyleSheetLoader {
+    type Asset = StyleSheet;
+    type Settings = ();
+    type Error = StyleSheetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<StyleSheet, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        StyleSheet::parse(&String::from_utf8(bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bss"]
+    }
+}
+
+/// Why a [`StyleSheet`] couldn't be loaded.
+#[derive(Debug)]
+pub enum StyleSheetError {
+    Io(std::io::Error),
+    Utf8(std::string::FromUtf8Error),
+    /// The stylesheet isn't valid, at the given line.
+    Parse {
+        line: usize,
+        reason: String,
+    },
+}
+
+impl Display for StyleSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "couldn't read the stylesheet: {error}"),
+            Self::Utf8(error) => write!(f, "the stylesheet isn't valid UTF-8: {error}"),
+            Self::Parse { line, reason } => write!(f, "{reason} (line {line})"),
+        }
+    }
+}
+
+impl std::error::Error for StyleSheetError {}
+
+impl From<std::io::Error> for StyleSheetError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<std::string::FromUtf8Error> for StyleSheetError {
+    fn from(error: std::string::FromUtf8Error) -> Self {
+        Self::Utf8(error)
+    }
+}
+
+/// Elements to restyle at the end of the frame.
+#[derive(Resource, Default)]
+struct PendingRestyles {
+    /// Roots whose elements should all be restyled.
+    roots: HashSet<Entity>,
+    /// Elements that should be restyled along with their descendants.
+    subtrees: HashSet<Entity>,
+}
+
+type ChangedElements<'w, 's> = Query<
+    'w,
+    's,
+    Entity,
+    (
+        With<ElementTag>,
+        Or<(
+            Added<ElementTag>,
+            Changed<Classes>,
+            Changed<InlineStyle>,
+            Changed<ChildOf>,
+            Changed<Interaction>,
+            Changed<PickingInteraction>,
+            Added<Disabled>,
+        )>,
+    ),
+>;
+
+fn collect_restyles(
+    mut events: EventReader<AssetEvent<StyleSheet>>,
+    roots: Query<(Entity, &DioxusRoot)>,
+    changed: ChangedElements,
+    mut removed_classes: RemovedComponents<Classes>,
+    mut removed_styles: RemovedComponents<InlineStyle>,
+    mut removed_disabled: RemovedComponents<Disabled>,
+    mut pending: ResMut<PendingRestyles>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id }
+        | AssetEvent::Modified { id }
+        | AssetEvent::Removed { id }) = event
+        else {
+            continue;
+        };
+        for (entity, root) in &roots {
+            if root.stylesheets.iter().any(|sheet| sheet.id() == *id) {
+                pending.roots.insert(entity);
+            }
+        }
+    }
+
+    pending.subtrees.extend(changed.iter());
+    pending.subtrees.extend(removed_classes.read());
+    pending.subtrees.extend(removed_styles.read());
+    pending.subtrees.extend(removed_disabled.read());
+}
+
+fn restyle(world: &mut World) {
+    let pending = std::mem::take(&mut *world.resource_mut::<PendingRestyles>());
+    if pending.roots.is_empty() && pending.subtrees.is_empty() {
+        return;
+    }
+
+    // Only elements with dynamic parts know their root, so restyled roots are walked down instead
+    let mut stack = pending
+        .roots
+        .iter()
+        .filter_map(|&root| world.get::<Children>(root))
+        .flat_map(|children| children.iter())
+        .chain(pending.subtrees)
+        .collect::<Vec<_>>();
+    let mut elements = HashSet::new();
+    while let Some(entity) = stack.pop() {
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            continue;
+        };
+        if entity_ref.contains::<ElementTag>() && elements.insert(entity) {
+            if let Some(children) = entity_ref.get::<Children>() {
+                stack.extend(children.iter());
+            }
+        }
+    }
+
+    for entity in elements {
+        restyle_element(world, entity);
+    }
+}
+
+/// Applies the rules of the stylesheets matching `entity`, resets the properties of the rules that
+/// stopped matching it, and applies its `style` attribute over them.
+fn restyle_element(world: &mut World, entity: Entity) {
+    // Elements that aren't in the tree yet are styled once they are inserted
+    let Some(root) = std::iter::successors(Some(entity), |&e| {
+        world.get::<ChildOf>(e).map(ChildOf::parent)
+    })
+    .find_map(|e| world.get::<DioxusRoot>(e)) else {
+        return;
+    };
+
+    let sheets = world.resource::<Assets<StyleSheet>>();
+    let mut matched = Vec::new();
+    let mut needs_interaction = false;
+    for rule in root
+        .stylesheets
+        .iter()
+        .filter_map(|sheet| sheets.get(sheet))
+        .flat_map(|sheet| &sheet.rules)
+    {
+        let specificity = rule
+            .selectors
+            .iter()
+            .filter(|selector| selector.matches(world, entity))
+            .map(Selector::specificity)
+            .max();
+        if let Some(specificity) = specificity {
+            matched.push((specificity, &rule.properties));
+        }
+
+        // Pseudo-classes can only match elements whose interaction is tracked
+        needs_interaction |= rule.selectors.iter().any(|selector| {
+            selector.compounds.iter().any(|compound| {
+                compound.states.iter().any(|s| *s != PseudoClass::Disabled)
+                    && compound.matches(world, entity, false)
+            })
+        });
+    }
+    // Stable, so rules of the same specificity keep their order
+    matched.sort_by_key(|(specificity, _)| *specificity);
+
+    let inline = world
+        .get::<InlineStyle>(entity)
+        .cloned()
+        .unwrap_or_default();
+    let properties = matched
+        .into_iter()
+        .flat_map(|(_, properties)| properties.iter().cloned())
+        .filter(|property| !inline.0.iter().any(|p| p.name() == property.name()))
+        .collect::<Vec<_>>();
+
+    let previous = world
+        .get::<MatchedStyle>(entity)
+        .cloned()
+        .unwrap_or_default();
+    if previous.0.is_empty() && properties.is_empty() {
+        if needs_interaction && !world.entity(entity).contains::<Interaction>() {
+            world.entity_mut(entity).insert(Interaction::default());
+        }
+        return;
+    }
+
+    // Properties the `style` attribute now declares stay overridden, by it
+    let removed = previous
+        .0
+        .into_iter()
+        .filter(|old| !properties.iter().any(|new| new.name() == old.name()))
+        .filter(|old| !inline.0.iter().any(|p| p.name() == old.name()))
+        .collect::<Vec<_>>();
+    reset_properties(world, entity, &removed);
+    override_properties(world, entity, &properties);
+    override_properties(world, entity, &inline.0);
+
+    let mut entity = world.entity_mut(entity);
+    if needs_interaction && !entity.contains::<Interaction>() {
+        entity.insert(Interaction::default());
+    }
+    if properties.is_empty() {
+        entity.remove::<MatchedStyle>();
+    } else if entity
+        .get::<MatchedStyle>()
+        .is_none_or(|m| m.0 != properties)
+    {
+        entity.insert(MatchedStyle(properties));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selector(text: &str) -> Selector {
+        Selector::parse(text).unwrap()
+    }
+
+    fn error_line(text: &str) -> usize {
+        match StyleSheet::parse(text) {
+            Err(StyleSheetError::Parse { line, .. }) => line,
+            parsed => panic!("`{text}` parsed as {parsed:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_rules() {
+        let sheet = StyleSheet::parse(
+            ".button, probe { width: 10px; padding: 1px }\n.button:hover { width: 20px }",
+        )
+        .unwrap();
+        assert_eq!(
+            sheet.rules,
+            vec![
+                StyleRule {
+                    selectors: vec![selector(".button"), selector("probe")],
+                    properties: parse_style("width: 10px; padding: 1px").unwrap(),
+                },
+                StyleRule {
+                    selectors: vec![selector(".button:hover")],
+                    properties: parse_style("width: 20px").unwrap(),
+                },
+            ]
+        );
+        assert_eq!(StyleSheet::parse(" \n ").unwrap(), StyleSheet::default());
+    }
+
+    #[test]
+    fn strips_comments() {
+        assert_eq!(
+            StyleSheet::parse(
+                "/* .a { width: 1px } */ .b /* } */ { /* width: 2px */ height: 3px }"
+            )
+            .unwrap(),
+            StyleSheet::parse(".b { height: 3px }").unwrap()
+        );
+        assert_eq!(error_line(".a { width: 1px }\n/* never\nends"), 2);
+    }
+
+    #[test]
+    fn reports_the_line_of_errors() {
+        assert_eq!(error_line(".a {\n  width: wide\n}"), 1);
+        assert_eq!(
+            error_line("/* a\n comment */\n.a { width: 1px }\n.b { width: wide }"),
+            4
+        );
+        assert_eq!(
+            error_line(".a { width: 1px }\n\n.a:focus { width: 2px }"),
+            3
+        );
+        assert_eq!(error_line(".a { width: 1px } }"), 1);
+        assert_eq!(error_line(".a\n{ width: 1px"), 1);
+        assert_eq!(error_line(".a { .b { width: 1px } }"), 1);
+    }
+
+    #[test]
+    fn parses_combinators() {
+        let Selector {
+            compounds,
+            combinators,
+        } = selector("node .menu>.item  > probe.a.b:hover:pressed");
+        assert_eq!(
+            combinators,
+            [Combinator::Descendant, Combinator::Child, Combinator::Child]
+        );
+        assert_eq!(compounds[0].tag.as_deref(), Some("node"));
+        assert_eq!(compounds[3].tag.as_deref(), Some("probe"));
+        assert_eq!(compounds[3].classes, ["a", "b"]);
+        assert_eq!(
+            compounds[3].states,
+            [PseudoClass::Hover, PseudoClass::Active]
+        );
+        assert_eq!(selector(".a>.b"), selector(".a > .b"));
+        assert_eq!(selector("*.a"), selector(".a"));
+    }
+
+    #[test]
+    fn rejects_misplaced_child_combinators() {
+        for text in ["> .a", ".a >", ".a > > .b", ">"] {
+            let error = Selector::parse(text).unwrap_err();
+            assert!(error.starts_with("misplaced `>`"), "{text}: {error}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        assert_eq!(
+            Selector::parse(".a:focus"),
+            Err("unsupported pseudo-class `:focus`".to_string())
+        );
+        assert!(Selector::parse("").is_err());
+        assert!(Selector::parse(".").is_err());
+        assert!(Selector::parse(".a..b").is_err());
+        assert!(Selector::parse("#id").is_err());
+        assert!(Selector::parse("[open]").is_err());
+    }
+
+    #[test]
+    fn orders_selectors_by_specificity() {
+        assert_eq!(selector("*").specificity(), (0, 0));
+        assert_eq!(selector("probe").specificity(), (0, 1));
+        assert_eq!(selector(".a").specificity(), (1, 0));
+        assert_eq!(selector("node > probe.a:hover").specificity(), (2, 2));
+
+        let mut selectors = ["node probe", ".a", "probe", "probe.a", ".a:hover", "*"].map(selector);
+        selectors.sort_by_key(Selector::specificity);
+        assert_eq!(
+            selectors,
+            ["*", "probe", "node probe", ".a", "probe.a", ".a:hover"].map(selector)
+        );
+    }
+}
@@ -0,0 +1,533 @@
+//! Rich text: the text children of a text element are rendered as [`TextSpan`]s of a single
+//! [`Text`] entity, so `text { "HP: " b { "{hp}" } }` is laid out as one paragraph.
+//!
+//! Any element with a `Text` or a `TextSpan` is a text element. The text nodes Dioxus creates are
+//! spans while they are inside one, and a `Node` with its own `Text` anywhere else. The `color`,
+//! `font_size`, `font` and `weight` attributes style a text element and the spans inside it, which
+//! inherit the styles they don't set from their parent, like in CSS, along with `line_height`.
+//! The `font` is either a `Handle<Font>`, or the path of an asset loaded through the
+//! `AssetServer`. `justify` and `linebreak` lay out a text element as a whole.
+
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::prelude::*;
+use bevy::text::{JustifyText, LineBreak, LineHeight};
+use bevy::ui::UiSystem;
+use dioxus_core::AttributeValue;
+
+use crate::component::{attr, convert_attribute};
+use crate::error::DioxusErrorKind;
+use crate::value::{downcast_any, parse_text, FromAttributeValue};
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    app.init_resource::<FontFaces>()
+        .init_resource::<LoadedFonts>()
+        .add_systems(
+            PostUpdate,
+            (place_text_fragments, propagate_text_styles)
+                .chain()
+                .before(UiSystem::Prepare),
+        );
+}
+
+/// Marks the entity of a text node, as opposed to an element.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct TextFragment;
+
+/// The styles set on a text element by its attributes. The spans inside it inherit the ones they
+/// don't set themselves.
+#[derive(Component, Debug, Clone, Default)]
+pub struct TextStyle {
+    pub color: Option<Color>,
+    pub font_size: Option<f32>,
+    pub line_height: Option<LineHeight>,
+    /// The family of the text, as the face of its regular weight.
+    pub font: Option<Handle<Font>>,
+    pub weight: Option<FontWeight>,
+}
+
+/// The weight of a font, from 100 (thin) to 900 (black). Text accepts a number, or `thin`,
+/// `light`, `normal`, `medium`, `semibold`, `bold` and `black`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const NORMAL: Self = Self(400);
+    pub const BOLD: Self = Self(700);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+impl FromAttributeValue for FontWeight {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        let weight = match &value {
+            AttributeValue::Text(text) => match text.trim() {
+                "thin" => 100,
+                "light" => 300,
+                "normal" => 400,
+                "medium" => 500,
+                "semibold" => 600,
+                "bold" => 700,
+                "black" => 900,
+                text => parse_text(text)?,
+            },
+            AttributeValue::Int(_) => u16::from_attribute_value(value)?,
+            value => {
+                return downcast_any(value).ok_or(DioxusErrorKind::TypeMismatch {
+                    expected: std::any::type_name::<Self>(),
+                })
+            }
+        };
+
+        if (1..=1000).contains(&weight) {
+            Ok(Self(weight))
+        } else {
+            Err(DioxusErrorKind::InvalidValue(format!(
+                "{weight} is not a font weight between 1 and 1000"
+            )))
+        }
+    }
+}
+
+/// The faces of each font family by weight, which the `weight` attribute picks from. A family is
+/// known by the face of its regular weight, and the default font by `Handle::default()`.
+///
+/// ```rust,ignore
+/// app.insert_resource(
+///     FontFaces::default()
+///         .with_face(&inter, FontWeight::BOLD, asset_server.load("fonts/Inter-Bold.ttf")),
+/// );
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct FontFaces {
+    pub families: HashMap<AssetId<Font>, BTreeMap<FontWeight, Handle<Font>>>,
+}
+
+impl FontFaces {
+    pub fn with_face(
+        mut self,
+        family: &Handle<Font>,
+        weight: FontWeight,
+        face: Handle<Font>,
+    ) -> Self {
+        self.families
+            .entry(family.id())
+            .or_default()
+            .insert(weight, face);
+        self
+    }
+
+    /// The face of `family` with the weight closest to `weight`. The family itself is its regular
+    /// face, unless another face was added for it.
+    pub fn face(&self, family: &Handle<Font>, weight: FontWeight) -> Handle<Font> {
+        let Some(faces) = self.families.get(&family.id()) else {
+            return family.clone();
+        };
+
+        faces
+            .iter()
+            .chain(
+                (!faces.contains_key(&FontWeight::NORMAL)).then_some((&FontWeight::NORMAL, family)),
+            )
+            .min_by_key(|(face_weight, _)| face_weight.0.abs_diff(weight.0))
+            .map_or_else(|| family.clone(), |(_, face)| face.clone())
+    }
+}
+
+/// Updates a field of the [`TextStyle`] of `entity`. A removed style is also reset on the entity
+/// itself, since a text element only has its components set while it has a style.
+fn set_text_style<T: FromAttributeValue>(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+    set: impl FnOnce(&mut TextStyle, Option<T>),
+    reset: impl FnOnce(&mut EntityWorldMut),
+) -> Result<(), DioxusErrorKind> {
+    let value = match value {
+        AttributeValue::None => None,
+        value => Some(convert_attribute::<T>(value)?),
+    };
+
+    let mut entity = world.entity_mut(entity);
+    if value.is_none() {
+        reset(&mut entity);
+    }
+    let mut style = entity.take::<TextStyle>().unwrap_or_default();
+    set(&mut style, value);
+    entity.insert(style);
+
+    Ok(())
+}
+
+pub(crate) fn set_color(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_text_style(
+        world,
+        entity,
+        value,
+        |style, color| style.color = color,
+        |entity| {
+            entity.insert(TextColor::default());
+        },
+    )
+}
+
+pub(crate) fn set_font_size(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_text_style(
+        world,
+        entity,
+        value,
+        |style, font_size| style.font_size = font_size,
+        |entity| {
+            if let Some(mut font) = entity.get_mut::<TextFont>() {
+                font.font_size = TextFont::default().font_size;
+            }
+        },
+    )
+}
+
+pub(crate) fn set_line_height(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_text_style(
+        world,
+        entity,
+        value,
+        |style, line_height| style.line_height = line_height,
+        |entity| {
+            if let Some(mut font) = entity.get_mut::<TextFont>() {
+                font.line_height = LineHeight::default();
+            }
+        },
+    )
+}
+
+/// Fonts loaded by path for the `font` attribute, kept so they stay loaded while no text uses
+/// them.
+#[derive(Resource, Default)]
+struct LoadedFonts(HashMap<String, Handle<Font>>);
+
+/// Sets the font of a text element to a `Handle<Font>`, or to the font at an asset path, which is
+/// loaded once through the `AssetServer`.
+pub(crate) fn set_font(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    let value = match value {
+        AttributeValue::Text(path) => attr(load_font(world, path)?),
+        value => value,
+    };
+
+    set_text_style(
+        world,
+        entity,
+        value,
+        |style, font: Option<Handle<Font>>| style.font = font,
+        reset_font,
+    )
+}
+
+pub(crate) fn set_weight(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_text_style(
+        world,
+        entity,
+        value,
+        |style, weight| style.weight = weight,
+        reset_font,
+    )
+}
+
+fn load_font(world: &mut World, path: String) -> Result<Handle<Font>, DioxusErrorKind> {
+    if let Some(font) = world.resource::<LoadedFonts>().0.get(&path) {
+        return Ok(font.clone());
+    }
+
+    let Some(asset_server) = world.get_resource::<AssetServer>() else {
+        return Err(DioxusErrorKind::InvalidValue(format!(
+            "`{path}` can't be loaded without the `AssetPlugin`"
+        )));
+    };
+    let font = asset_server.load(&path);
+    world
+        .resource_mut::<LoadedFonts>()
+        .0
+        .insert(path, font.clone());
+    Ok(font)
+}
+
+/// Sets a field of the [`TextLayout`] of a text element, which spans don't have.
+fn set_text_layout<T: FromAttributeValue + Default>(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+    set: impl FnOnce(&mut TextLayout, T),
+) -> Result<(), DioxusErrorKind> {
+    let value = match value {
+        AttributeValue::None => T::default(),
+        value => convert_attribute::<T>(value)?,
+    };
+
+    let Some(mut layout) = world.get_mut::<TextLayout>(entity) else {
+        return Err(DioxusErrorKind::MissingComponent("TextLayout"));
+    };
+    set(&mut layout, value);
+    Ok(())
+}
+
+pub(crate) fn set_justify(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_text_layout(world, entity, value, |layout, justify: JustifyText| {
+        layout.justify = justify
+    })
+}
+
+pub(crate) fn set_linebreak(
+    world: &mut World,
+    entity: Entity,
+    value: AttributeValue,
+) -> Result<(), DioxusErrorKind> {
+    set_text_layout(world, entity, value, |layout, linebreak: LineBreak| {
+        layout.linebreak = linebreak
+    })
+}
+
+fn reset_font(entity: &mut EntityWorldMut) {
+    if let Some(mut font) = entity.get_mut::<TextFont>() {
+        font.font = Handle::default();
+    }
+}
+
+type PlacedFragments<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static ChildOf,
+        Option<&'static Text>,
+        Option<&'static TextSpan>,
+    ),
+    (With<TextFragment>, Changed<ChildOf>),
+>;
+
+type TextElements<'w, 's> = Query<'w, 's, (), Or<(With<Text>, With<TextSpan>)>>;
+
+/// Makes text nodes spans of the text element they were inserted in, or standalone text anywhere
+/// else.
+fn place_text_fragments(
+    fragments: PlacedFragments,
+    text_elements: TextElements,
+    mut commands: Commands,
+) {
+    for (entity, child_of, text, span) in &fragments {
+        let in_text = text_elements.contains(child_of.parent());
+        match (in_text, text, span) {
+            (true, Some(text), _) => {
+                commands
+                    .entity(entity)
+                    .remove::<(Text, Node)>()
+                    .insert(TextSpan(text.0.clone()));
+            }
+            (false, _, Some(span)) => {
+                commands
+                    .entity(entity)
+                    .remove::<TextSpan>()
+                    .insert(Text(span.0.clone()));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The styles a span inherits from its parent.
+struct InheritedStyle {
+    color: Color,
+    font_size: f32,
+    line_height: LineHeight,
+    family: Handle<Font>,
+    weight: FontWeight,
+}
+
+type TextRoots<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static TextStyle>,
+        &'static mut TextColor,
+        &'static mut TextFont,
+        Option<&'static Children>,
+    ),
+    (With<Text>, Without<TextSpan>),
+>;
+
+type TextSpans<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static TextStyle>,
+        &'static mut TextColor,
+        &'static mut TextFont,
+        Option<&'static Children>,
+    ),
+    (With<TextSpan>, Without<Text>),
+>;
+
+type ChangedRoots<'w, 's> = Query<
+    'w,
+    's,
+    Entity,
+    (
+        With<Text>,
+        Or<(
+            Changed<TextStyle>,
+            Changed<TextColor>,
+            Changed<TextFont>,
+            Changed<Children>,
+        )>,
+    ),
+>;
+
+type ChangedSpans<'w, 's> = Query<
+    'w,
+    's,
+    Entity,
+    (
+        With<TextSpan>,
+        Or<(Added<TextSpan>, Changed<TextStyle>, Changed<ChildOf>)>,
+    ),
+>;
+
+/// Applies the styles of text elements, and of the spans inside them, to their `TextColor` and
+/// `TextFont`.
+fn propagate_text_styles(
+    faces: Res<FontFaces>,
+    changed_spans: ChangedSpans,
+    all_roots: Query<Entity, With<Text>>,
+    parents: Query<&ChildOf>,
+    // Roots change their own colors and fonts, which they are also restyled for
+    mut roots: ParamSet<(ChangedRoots, TextRoots)>,
+    mut spans: TextSpans,
+) {
+    let mut dirty = if faces.is_changed() {
+        all_roots.iter().collect::<Vec<_>>()
+    } else {
+        roots.p0().iter().collect::<Vec<_>>()
+    };
+    for span in &changed_spans {
+        if let Some(root) = parents
+            .iter_ancestors(span)
+            .find(|&ancestor| all_roots.contains(ancestor))
+        {
+            dirty.push(root);
+        }
+    }
+    dirty.sort_unstable();
+    dirty.dedup();
+
+    for root in dirty {
+        let mut roots = roots.p1();
+        let Ok((style, mut color, mut font, children)) = roots.get_mut(root) else {
+            continue;
+        };
+
+        let style = style.cloned().unwrap_or_default();
+        if let Some(value) = style.color {
+            color.set_if_neq(TextColor(value));
+        }
+        if let Some(value) = style.font_size {
+            if font.font_size != value {
+                font.font_size = value;
+            }
+        }
+        if let Some(value) = style.line_height {
+            if !same_line_height(font.line_height, value) {
+                font.line_height = value;
+            }
+        }
+        let family = match (&style.font, style.weight) {
+            (Some(family), _) => family.clone(),
+            (None, Some(_)) => Handle::default(),
+            (None, None) => font.font.clone(),
+        };
+        let weight = style.weight.unwrap_or_default();
+        if style.font.is_some() || style.weight.is_some() {
+            let face = faces.face(&family, weight);
+            if font.font != face {
+                font.font = face;
+            }
+        }
+
+        let inherited = InheritedStyle {
+            color: color.0,
+            font_size: font.font_size,
+            line_height: font.line_height,
+            family,
+            weight,
+        };
+        let children = children.map(|c| c.to_vec()).unwrap_or_default();
+        for child in children {
+            style_span(&faces, &mut spans, child, &inherited);
+        }
+    }
+}
+
+fn style_span(faces: &FontFaces, spans: &mut TextSpans, entity: Entity, parent: &InheritedStyle) {
+    let Ok((style, mut color, mut font, children)) = spans.get_mut(entity) else {
+        return;
+    };
+
+    let style = style.cloned().unwrap_or_default();
+    let inherited = InheritedStyle {
+        color: style.color.unwrap_or(parent.color),
+        font_size: style.font_size.unwrap_or(parent.font_size),
+        line_height: style.line_height.unwrap_or(parent.line_height),
+        family: style.font.unwrap_or_else(|| parent.family.clone()),
+        weight: style.weight.unwrap_or(parent.weight),
+    };
+
+    color.set_if_neq(TextColor(inherited.color));
+    if font.font_size != inherited.font_size {
+        font.font_size = inherited.font_size;
+    }
+    if !same_line_height(font.line_height, inherited.line_height) {
+        font.line_height = inherited.line_height;
+    }
+    let face = faces.face(&inherited.family, inherited.weight);
+    if font.font != face {
+        font.font = face;
+    }
+
+    let children = children.map(|c| c.to_vec()).unwrap_or_default();
+    for child in children {
+        style_span(faces, spans, child, &inherited);
+    }
+}
+
+/// `LineHeight` isn't `PartialEq`, and writing it unconditionally would re-layout the text.
+fn same_line_height(a: LineHeight, b: LineHeight) -> bool {
+    match (a, b) {
+        (LineHeight::Px(a), LineHeight::Px(b)) => a == b,
+        (LineHeight::RelativeToFont(a), LineHeight::RelativeToFont(b)) => a == b,
+        _ => false,
+    }
+}
@@ -0,0 +1,165 @@
+//! Design tokens: named colors, spacings, font sizes and radii in a [`Theme`] resource, which
+//! attributes refer to with [`token`] instead of a value, like `background_color: token("surface")`.
+//!
+//! A token is resolved when the attribute is set, and every attribute referring to a token is set
+//! again when the `Theme` changes, so switching between light, dark or high contrast themes
+//! restyles the UI without re-rendering any component. A token the theme doesn't define is
+//! reported as a [`DioxusError`](crate::error::DioxusError).
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use dioxus_core::AttributeValue;
+
+use crate::component::attr;
+use crate::error::DioxusErrorKind;
+use crate::native::{AttributeValues, DioxusElement, ElementTag};
+use crate::value::downcast_any;
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        reapply_tokens
+            .run_if(resource_exists_and_changed::<Theme>)
+            .after(crate::native::process_commands),
+    );
+}
+
+/// The tokens attributes can refer to with [`token`].
+///
+/// ```rust,ignore
+/// app.insert_resource(
+///     Theme::default()
+///         .with_color("surface", Color::srgb(0.1, 0.1, 0.12))
+///         .with_spacing("gap", Val::Px(8.0))
+///         .with_font_size("body", 16.0)
+///         .with_radius("card", Val::Px(6.0)),
+/// );
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Default)]
+pub struct Theme {
+    pub tokens: HashMap<String, ThemeValue>,
+}
+
+/// The value of a token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThemeValue {
+    Color(Color),
+    /// A spacing or a radius, for [`Val`], [`UiRect`] and [`BorderRadius`] attributes.
+    Length(Val),
+    /// A font size, or any other number.
+    Number(f32),
+}
+
+impl From<Color> for ThemeValue {
+    fn from(color: Color) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl From<Val> for ThemeValue {
+    fn from(length: Val) -> Self {
+        Self::Length(length)
+    }
+}
+
+impl From<f32> for ThemeValue {
+    fn from(number: f32) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl ThemeValue {
+    /// The value as an attribute value, which any attribute accepting the value converts.
+    fn to_attribute_value(self) -> AttributeValue {
+        match self {
+            Self::Color(color) => attr(color),
+            Self::Length(length) => attr(length),
+            Self::Number(number) => AttributeValue::Float(number as f64),
+        }
+    }
+}
+
+impl Theme {
+    pub fn get(&self, name: &str) -> Option<ThemeValue> {
+        self.tokens.get(name).copied()
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<ThemeValue>) {
+        self.tokens.insert(name.into(), value.into());
+    }
+
+    pub fn with_color(mut self, name: impl Into<String>, color: impl Into<Color>) -> Self {
+        self.set(name, color.into());
+        self
+    }
+
+    pub fn with_spacing(mut self, name: impl Into<String>, spacing: Val) -> Self {
+        self.set(name, spacing);
+        self
+    }
+
+    pub fn with_font_size(mut self, name: impl Into<String>, size: f32) -> Self {
+        self.set(name, size);
+        self
+    }
+
+    pub fn with_radius(mut self, name: impl Into<String>, radius: Val) -> Self {
+        self.set(name, radius);
+        self
+    }
+}
+
+/// A reference to a token of the [`Theme`], made with [`token`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThemeToken(pub String);
+
+/// Sets an attribute to the value of the token called `name` in the [`Theme`], and keeps it in
+/// sync when the theme changes.
+pub fn token(name: impl Into<String>) -> AttributeValue {
+    attr(ThemeToken(name.into()))
+}
+
+/// Replaces a [`token`] with its value in the theme. Other values are returned as they are.
+pub(crate) fn resolve_token(
+    world: &World,
+    value: AttributeValue,
+) -> Result<AttributeValue, DioxusErrorKind> {
+    let Some(ThemeToken(name)) = downcast_any::<ThemeToken>(&value) else {
+        return Ok(value);
+    };
+
+    world
+        .get_resource::<Theme>()
+        .and_then(|theme| theme.get(&name))
+        .map(ThemeValue::to_attribute_value)
+        .ok_or(DioxusErrorKind::UnknownToken(name))
+}
+
+/// Sets every attribute that refers to a token again, with its value in the new theme.
+fn reapply_tokens(world: &mut World) {
+    let bound = world
+        .non_send_resource::<AttributeValues>()
+        .values
+        .iter()
+        .flat_map(|(&entity, values)| {
+            values
+                .iter()
+                .filter(|(_, value)| downcast_any::<ThemeToken>(value).is_some())
+                .map(move |(&name, value)| (entity, name, value.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    for (entity, name, value) in bound {
+        let Some(element) = world.get::<DioxusElement>(entity).copied() else {
+            continue;
+        };
+        let tag = world.get::<ElementTag>(entity).map(|tag| tag.0);
+
+        if let Err(error) =
+            crate::renderers::apply_attribute(world, element.root, entity, tag, name, value)
+        {
+            crate::error::report(world, error.with_element(element.id));
+        }
+    }
+}
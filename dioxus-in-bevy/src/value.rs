@@ -0,0 +1,1145 @@
+//! Conversion of Dioxus attribute values into the types attributes are declared with.
+//!
+//! Dioxus hands attributes over as an [`AttributeValue`]: Rust literals arrive as `Int`, `Float`,
+//! `Text` or `Bool`, and anything wrapped in [`attr`](crate::component::attr) as `Any`. Types that
+//! implement [`FromAttributeValue`] accept the variants that make sense for them, so `width: 10`
+//! works for an `f32` or a [`Val`] attribute. Other types can still be passed with `attr(..)`, as
+//! long as the value is of exactly that type.
+//!
+//! Attributes written as literals in `rsx!` are static, and always arrive as `Text`. The built-in
+//! implementations parse text into their type, so `width: "50%"`, `background_color: "#ff0000"`
+//! or `flex_direction: "column"` mean the same whether they are static or computed.
+
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use bevy::reflect::{DynamicEnum, DynamicVariant, TypeInfo, Typed, VariantInfo};
+use bevy::sprite::{BorderRect, TextureSlicer};
+use bevy::text::{JustifyText, LineBreak, LineHeight};
+use bevy::ui::{
+    AlignContent, AlignItems, AlignSelf, BoxShadow, BoxSizing, Display, FlexDirection, FlexWrap,
+    GridAutoFlow, GridPlacement, GridTrack, GridTrackRepetition, JustifyContent, JustifyItems,
+    JustifySelf, MaxTrackSizingFunction, MinTrackSizingFunction, OverflowAxis, OverflowClipBox,
+    PositionType, RepeatedGridTrack, ShadowStyle,
+};
+use dioxus_core::{AnyValue, AttributeValue};
+
+use crate::error::DioxusErrorKind;
+
+/// A type an attribute can be declared with, converted from the values Dioxus passes.
+pub trait FromAttributeValue: Sized {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind>;
+}
+
+/// The value wrapped in [`attr`](crate::component::attr), if it is exactly a `T`.
+pub fn downcast_any<T: AnyValue + Clone>(value: &AttributeValue) -> Option<T> {
+    match value {
+        AttributeValue::Any(any) => any.as_any().downcast_ref::<T>().cloned(),
+        _ => None,
+    }
+}
+
+fn type_mismatch<T>() -> DioxusErrorKind {
+    DioxusErrorKind::TypeMismatch {
+        expected: std::any::type_name::<T>(),
+    }
+}
+
+fn invalid_text<T>(text: &str) -> DioxusErrorKind {
+    DioxusErrorKind::InvalidValue(format!(
+        "`{text}` is not a valid {}",
+        std::any::type_name::<T>()
+    ))
+}
+
+/// Parses text with [`FromStr`](std::str::FromStr), ignoring surrounding whitespace.
+pub(crate) fn parse_text<T: std::str::FromStr>(text: &str) -> Result<T, DioxusErrorKind> {
+    text.trim().parse().map_err(|_| invalid_text::<T>(text))
+}
+
+/// Parses a CSS length: `auto`, a number of pixels with or without `px`, or a number followed by
+/// `%`, `vw`, `vh`, `vmin` or `vmax`.
+pub fn parse_val(text: &str) -> Result<Val, DioxusErrorKind> {
+    let trimmed = text.trim();
+    if trimmed.eq_ignore_ascii_case("auto") {
+        return Ok(Val::Auto);
+    }
+
+    type Unit = fn(f32) -> Val;
+    let units: [(&str, Unit); 6] = [
+        ("px", Val::Px),
+        ("%", Val::Percent),
+        ("vw", Val::Vw),
+        ("vh", Val::Vh),
+        ("vmin", Val::VMin),
+        ("vmax", Val::VMax),
+    ];
+    let (number, unit) = units
+        .iter()
+        .find_map(|(suffix, unit)| Some((trimmed.strip_suffix(suffix)?, *unit)))
+        .unwrap_or((trimmed, Val::Px));
+
+    number
+        .trim()
+        .parse()
+        .map(unit)
+        .map_err(|_| invalid_text::<Val>(text))
+}
+
+/// Parses a CSS shorthand of one to four lengths: all sides, vertical and horizontal, top,
+/// horizontal and bottom, or top, right, bottom and left.
+pub fn parse_ui_rect(text: &str) -> Result<UiRect, DioxusErrorKind> {
+    let values = text
+        .split_whitespace()
+        .map(parse_val)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match values[..] {
+        [all] => Ok(UiRect::all(all)),
+        [vertical, horizontal] => Ok(UiRect::axes(horizontal, vertical)),
+        [top, horizontal, bottom] => Ok(UiRect::new(horizontal, horizontal, top, bottom)),
+        [top, right, bottom, left] => Ok(UiRect::new(left, right, top, bottom)),
+        _ => Err(invalid_text::<UiRect>(text)),
+    }
+}
+
+/// Parses a color: a hex code like `#f00`, `#ff0000` or `#ff000080`, `rgb(255, 0, 0)`,
+/// `rgba(255, 0, 0, 0.5)`, `transparent`, or one of the basic CSS color names like `red`.
+pub fn parse_color(text: &str) -> Result<Color, DioxusErrorKind> {
+    use bevy::color::palettes::{basic, css};
+
+    let trimmed = text.trim();
+    let named = match trimmed.to_ascii_lowercase().as_str() {
+        "transparent" => Some(Color::NONE),
+        "black" => Some(basic::BLACK.into()),
+        "silver" => Some(basic::SILVER.into()),
+        "gray" | "grey" => Some(basic::GRAY.into()),
+        "white" => Some(basic::WHITE.into()),
+        "maroon" => Some(basic::MAROON.into()),
+        "red" => Some(basic::RED.into()),
+        "purple" => Some(basic::PURPLE.into()),
+        "fuchsia" | "magenta" => Some(basic::FUCHSIA.into()),
+        "green" => Some(basic::GREEN.into()),
+        "lime" => Some(basic::LIME.into()),
+        "olive" => Some(basic::OLIVE.into()),
+        "yellow" => Some(basic::YELLOW.into()),
+        "navy" => Some(basic::NAVY.into()),
+        "blue" => Some(basic::BLUE.into()),
+        "teal" => Some(basic::TEAL.into()),
+        "aqua" | "cyan" => Some(basic::AQUA.into()),
+        "orange" => Some(css::ORANGE.into()),
+        _ => None,
+    };
+    if let Some(color) = named {
+        return Ok(color);
+    }
+    if trimmed.starts_with('#') {
+        return Srgba::hex(trimmed)
+            .map(Color::from)
+            .map_err(|_| invalid_text::<Color>(text));
+    }
+
+    let channels = trimmed
+        .strip_prefix("rgba(")
+        .or_else(|| trimmed.strip_prefix("rgb("))
+        .and_then(|channels| channels.strip_suffix(')'))
+        .ok_or_else(|| invalid_text::<Color>(text))?
+        .split(',')
+        .map(|channel| channel.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid_text::<Color>(text))?;
+
+    match channels[..] {
+        [red, green, blue] => Ok(Color::srgb_u8(red as u8, green as u8, blue as u8)),
+        [red, green, blue, alpha] => Ok(Color::srgba(
+            red / 255.0,
+            green / 255.0,
+            blue / 255.0,
+            alpha,
+        )),
+        _ => Err(invalid_text::<Color>(text)),
+    }
+}
+
+/// Parses the name of a unit variant of a reflected enum. Case, `-` and `_` are ignored, so
+/// `space-between`, `space_between` and `SpaceBetween` are the same variant.
+pub fn parse_enum<T: FromReflect + Typed>(text: &str) -> Result<T, DioxusErrorKind> {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(|c| *c != '-' && *c != '_')
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+
+    let TypeInfo::Enum(info) = T::type_info() else {
+        return Err(type_mismatch::<T>());
+    };
+    let wanted = normalize(text.trim());
+    let variant = info
+        .iter()
+        .find(|variant| {
+            matches!(variant, VariantInfo::Unit(_)) && normalize(variant.name()) == wanted
+        })
+        .ok_or_else(|| invalid_text::<T>(text))?;
+
+    T::from_reflect(&DynamicEnum::new(variant.name(), DynamicVariant::Unit))
+        .ok_or_else(|| invalid_text::<T>(text))
+}
+
+/// Splits text at `separator`, except inside parentheses, so `repeat(2, 1fr) auto` or
+/// `rgb(0, 0, 0)` stay whole.
+fn split_outside_parentheses(text: &str, separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && separator(c) => {
+                parts.push(&text[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// The text between `name(` and a closing `)`, if `text` is a call to `name`.
+fn strip_function<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    let arguments = text.get(..name.len())?;
+    if !arguments.eq_ignore_ascii_case(name) {
+        return None;
+    }
+    text[name.len()..]
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')
+}
+
+/// Parses a CSS shorthand of one to four radii: all corners, top left and bottom right with top
+/// right and bottom left, or top left, top right, bottom right and bottom left.
+pub fn parse_border_radius(text: &str) -> Result<BorderRadius, DioxusErrorKind> {
+    let values = text
+        .split_whitespace()
+        .map(parse_val)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match values[..] {
+        [all] => Ok(BorderRadius::all(all)),
+        [main, cross] => Ok(BorderRadius::new(main, cross, main, cross)),
+        [top_left, cross, bottom_right] => {
+            Ok(BorderRadius::new(top_left, cross, bottom_right, cross))
+        }
+        [top_left, top_right, bottom_right, bottom_left] => Ok(BorderRadius::new(
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        )),
+        _ => Err(invalid_text::<BorderRadius>(text)),
+    }
+}
+
+/// Parses the overflow of both axes, or of the horizontal and then the vertical axis, like
+/// `hidden` or `visible scroll`.
+pub fn parse_overflow(text: &str) -> Result<Overflow, DioxusErrorKind> {
+    let axes = text
+        .split_whitespace()
+        .map(parse_enum::<OverflowAxis>)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match axes[..] {
+        [both] => Ok(Overflow { x: both, y: both }),
+        [x, y] => Ok(Overflow { x, y }),
+        _ => Err(invalid_text::<Overflow>(text)),
+    }
+}
+
+fn parse_min_track_sizing(text: &str) -> Option<MinTrackSizingFunction> {
+    match text.to_ascii_lowercase().as_str() {
+        "min-content" => return Some(MinTrackSizingFunction::MinContent),
+        "max-content" => return Some(MinTrackSizingFunction::MaxContent),
+        _ => {}
+    }
+    Some(match parse_val(text).ok()? {
+        Val::Auto => MinTrackSizingFunction::Auto,
+        Val::Px(value) => MinTrackSizingFunction::Px(value),
+        Val::Percent(value) => MinTrackSizingFunction::Percent(value),
+        Val::Vw(value) => MinTrackSizingFunction::Vw(value),
+        Val::Vh(value) => MinTrackSizingFunction::Vh(value),
+        Val::VMin(value) => MinTrackSizingFunction::VMin(value),
+        Val::VMax(value) => MinTrackSizingFunction::VMax(value),
+    })
+}
+
+fn parse_max_track_sizing(text: &str) -> Option<MaxTrackSizingFunction> {
+    match text.to_ascii_lowercase().as_str() {
+        "min-content" => return Some(MaxTrackSizingFunction::MinContent),
+        "max-content" => return Some(MaxTrackSizingFunction::MaxContent),
+        _ => {}
+    }
+    if let Some(fraction) = text.strip_suffix("fr") {
+        return fraction
+            .trim()
+            .parse()
+            .ok()
+            .map(MaxTrackSizingFunction::Fraction);
+    }
+    if let Some(limit) = strip_function(text, "fit-content") {
+        return match parse_val(limit).ok()? {
+            Val::Px(value) => Some(MaxTrackSizingFunction::FitContentPx(value)),
+            Val::Percent(value) => Some(MaxTrackSizingFunction::FitContentPercent(value)),
+            _ => None,
+        };
+    }
+    Some(match parse_val(text).ok()? {
+        Val::Auto => MaxTrackSizingFunction::Auto,
+        Val::Px(value) => MaxTrackSizingFunction::Px(value),
+        Val::Percent(value) => MaxTrackSizingFunction::Percent(value),
+        Val::Vw(value) => MaxTrackSizingFunction::Vw(value),
+        Val::Vh(value) => MaxTrackSizingFunction::Vh(value),
+        Val::VMin(value) => MaxTrackSizingFunction::VMin(value),
+        Val::VMax(value) => MaxTrackSizingFunction::VMax(value),
+    })
+}
+
+/// Parses a single grid track: a length, `auto`, `min-content`, `max-content`, a fraction like
+/// `1fr`, `fit-content(100px)` or `minmax(100px, 1fr)`.
+pub fn parse_grid_track(text: &str) -> Result<GridTrack, DioxusErrorKind> {
+    let trimmed = text.trim();
+    let (min, max) = match strip_function(trimmed, "minmax") {
+        Some(arguments) => match split_outside_parentheses(arguments, |c| c == ',')[..] {
+            [min, max] => (parse_min_track_sizing(min), parse_max_track_sizing(max)),
+            _ => (None, None),
+        },
+        // Like CSS, a fraction or a fit-content limit only bounds the track from above
+        None if trimmed.ends_with("fr") || strip_function(trimmed, "fit-content").is_some() => (
+            Some(MinTrackSizingFunction::Auto),
+            parse_max_track_sizing(trimmed),
+        ),
+        None => (
+            parse_min_track_sizing(trimmed),
+            parse_max_track_sizing(trimmed),
+        ),
+    };
+
+    match (min, max) {
+        (Some(min), Some(max)) => Ok(GridTrack::minmax(min, max)),
+        _ => Err(invalid_text::<GridTrack>(text)),
+    }
+}
+
+/// Parses a list of grid tracks separated by whitespace, like `100px 1fr auto`.
+pub fn parse_grid_tracks(text: &str) -> Result<Vec<GridTrack>, DioxusErrorKind> {
+    split_outside_parentheses(text, char::is_whitespace)
+        .into_iter()
+        .map(parse_grid_track)
+        .collect()
+}
+
+/// Parses a grid template: grid tracks, and `repeat(count, tracks)` where the count is a number,
+/// `auto-fill` or `auto-fit`, like `200px repeat(3, 1fr)`.
+pub fn parse_repeated_grid_tracks(text: &str) -> Result<Vec<RepeatedGridTrack>, DioxusErrorKind> {
+    split_outside_parentheses(text, char::is_whitespace)
+        .into_iter()
+        .map(|track| {
+            let Some(arguments) = strip_function(track, "repeat") else {
+                return parse_grid_track(track).map(RepeatedGridTrack::from);
+            };
+            let Some((count, tracks)) = arguments.split_once(',') else {
+                return Err(invalid_text::<RepeatedGridTrack>(track));
+            };
+            let repetition = match count.trim().to_ascii_lowercase().as_str() {
+                "auto-fill" => GridTrackRepetition::AutoFill,
+                "auto-fit" => GridTrackRepetition::AutoFit,
+                count => GridTrackRepetition::Count(
+                    count
+                        .parse()
+                        .map_err(|_| invalid_text::<RepeatedGridTrack>(track))?,
+                ),
+            };
+            Ok(RepeatedGridTrack::repeat_many(
+                repetition,
+                parse_grid_tracks(tracks)?,
+            ))
+        })
+        .collect()
+}
+
+/// Parses a CSS grid line placement: `auto`, a start line like `2`, a span like `span 2`, or a
+/// start and an end separated by `/`, like `1 / 3` or `2 / span 3`. Lines can't be `0`.
+pub fn parse_grid_placement(text: &str) -> Result<GridPlacement, DioxusErrorKind> {
+    let invalid = || invalid_text::<GridPlacement>(text);
+    let mut placement = GridPlacement::auto();
+    let mut spanned = false;
+
+    for (side, part) in text.split('/').enumerate() {
+        let part = part.trim();
+        if side > 1 || part.is_empty() {
+            return Err(invalid());
+        }
+        if part.eq_ignore_ascii_case("auto") {
+            continue;
+        }
+        if let Some(span) = part
+            .get(..4)
+            .filter(|keyword| keyword.eq_ignore_ascii_case("span"))
+            .map(|_| &part[4..])
+        {
+            let span = span
+                .trim()
+                .parse::<std::num::NonZeroU16>()
+                .map_err(|_| invalid())?;
+            if spanned {
+                return Err(invalid());
+            }
+            spanned = true;
+            placement = placement.set_span(span.get());
+            continue;
+        }
+
+        let line = part
+            .parse::<std::num::NonZeroI16>()
+            .map_err(|_| invalid())?
+            .get();
+        placement = if side == 0 {
+            placement.set_start(line)
+        } else {
+            placement.set_end(line)
+        };
+    }
+
+    Ok(placement)
+}
+
+/// Parses CSS box shadows separated by commas, each made of a horizontal and a vertical offset,
+/// then optionally a blur and a spread radius, and a color, like `2px 4px 8px #00000080`. The
+/// color defaults to black, and `none` is no shadow.
+pub fn parse_box_shadow(text: &str) -> Result<BoxShadow, DioxusErrorKind> {
+    if text.trim().eq_ignore_ascii_case("none") {
+        return Ok(BoxShadow::default());
+    }
+
+    let shadows = split_outside_parentheses(text, |c| c == ',')
+        .into_iter()
+        .map(|shadow| {
+            let mut lengths = Vec::new();
+            let mut color = None;
+            for part in split_outside_parentheses(shadow, char::is_whitespace) {
+                match parse_val(part) {
+                    Ok(length) => lengths.push(length),
+                    Err(_) if color.is_none() => color = Some(parse_color(part)?),
+                    Err(_) => return Err(invalid_text::<BoxShadow>(text)),
+                }
+            }
+
+            match lengths[..] {
+                [x_offset, y_offset, ref rest @ ..] if rest.len() <= 2 => Ok(ShadowStyle {
+                    color: color.unwrap_or(Color::BLACK),
+                    x_offset,
+                    y_offset,
+                    blur_radius: rest.first().copied().unwrap_or(Val::ZERO),
+                    spread_radius: rest.get(1).copied().unwrap_or(Val::ZERO),
+                }),
+                _ => Err(invalid_text::<BoxShadow>(text)),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if shadows.is_empty() {
+        return Err(invalid_text::<BoxShadow>(text));
+    }
+    Ok(BoxShadow(shadows))
+}
+
+/// Parses a CSS line height: `normal`, a length in pixels like `24px`, or a multiple of the font
+/// size, as a number like `1.5` or a percentage like `150%`.
+pub fn parse_line_height(text: &str) -> Result<LineHeight, DioxusErrorKind> {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("normal") {
+        return Ok(LineHeight::default());
+    }
+
+    if let Some(pixels) = text.strip_suffix("px") {
+        parse_text(pixels).map(LineHeight::Px)
+    } else if let Some(percent) = text.strip_suffix('%') {
+        parse_text::<f32>(percent).map(|percent| LineHeight::RelativeToFont(percent / 100.0))
+    } else {
+        parse_text(text).map(LineHeight::RelativeToFont)
+    }
+    .map_err(|_| invalid_text::<LineHeight>(text))
+}
+
+/// Parses how an image fills its node: `auto`, `stretch`, `tiled`, `tiled-x` or `tiled-y`, which
+/// repeat the image along both or one axis, optionally followed by the scale past which it
+/// repeats, or `sliced` followed by one to four borders in pixels, in the order of a CSS
+/// `border-image-slice`, for a 9-sliced image.
+pub fn parse_image_mode(text: &str) -> Result<NodeImageMode, DioxusErrorKind> {
+    let mut words = text.split_whitespace();
+    let mode = words.next().unwrap_or_default().to_ascii_lowercase();
+    let rest = words.collect::<Vec<_>>();
+
+    let tiles = match mode.as_str() {
+        "auto" | "stretch" if !rest.is_empty() => return Err(invalid_text::<NodeImageMode>(text)),
+        "auto" => return Ok(NodeImageMode::Auto),
+        "stretch" => return Ok(NodeImageMode::Stretch),
+        "tiled" => (true, true),
+        "tiled-x" | "tiled_x" => (true, false),
+        "tiled-y" | "tiled_y" => (false, true),
+        "sliced" => {
+            let borders = rest
+                .iter()
+                .map(|border| parse_text::<f32>(border.strip_suffix("px").unwrap_or(border)))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid_text::<NodeImageMode>(text))?;
+            let border = match borders[..] {
+                [all] => BorderRect::all(all),
+                [vertical, horizontal] => BorderRect::axes(horizontal, vertical),
+                [top, horizontal, bottom] => BorderRect {
+                    left: horizontal,
+                    right: horizontal,
+                    top,
+                    bottom,
+                },
+                [top, right, bottom, left] => BorderRect {
+                    left,
+                    right,
+                    top,
+                    bottom,
+                },
+                _ => return Err(invalid_text::<NodeImageMode>(text)),
+            };
+            return Ok(NodeImageMode::Sliced(TextureSlicer {
+                border,
+                ..default()
+            }));
+        }
+        _ => return Err(invalid_text::<NodeImageMode>(text)),
+    };
+
+    let stretch_value = match rest[..] {
+        [] => 1.0,
+        [value] => parse_text(value).map_err(|_| invalid_text::<NodeImageMode>(text))?,
+        _ => return Err(invalid_text::<NodeImageMode>(text)),
+    };
+    Ok(NodeImageMode::Tiled {
+        tile_x: tiles.0,
+        tile_y: tiles.1,
+        stretch_value,
+    })
+}
+
+impl FromAttributeValue for String {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Text(text) => Ok(text),
+            value => downcast_any(&value).ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+impl FromAttributeValue for bool {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Bool(bool) => Ok(bool),
+            AttributeValue::Text(text) => parse_text(&text),
+            value => downcast_any(&value).ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+macro_rules! integer_attribute_values {
+    ($($integer:ty),*) => {
+        $(
+            impl FromAttributeValue for $integer {
+                fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+                    match value {
+                        AttributeValue::Int(number) => Self::try_from(number).map_err(|_| {
+                            DioxusErrorKind::InvalidValue(format!(
+                                "{number} doesn't fit in {}",
+                                stringify!($integer)
+                            ))
+                        }),
+                        AttributeValue::Text(text) => parse_text(&text),
+                        value => downcast_any(&value).ok_or_else(type_mismatch::<Self>),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer_attribute_values!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! float_attribute_values {
+    ($($float:ty),*) => {
+        $(
+            impl FromAttributeValue for $float {
+                fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+                    match value {
+                        AttributeValue::Float(number) => Ok(number as $float),
+                        AttributeValue::Int(number) => Ok(number as $float),
+                        AttributeValue::Text(text) => parse_text(&text),
+                        value => downcast_any(&value).ok_or_else(type_mismatch::<Self>),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+float_attribute_values!(f32, f64);
+
+/// Numbers are lengths in logical pixels, and text is parsed with [`parse_val`].
+impl FromAttributeValue for Val {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Float(_) | AttributeValue::Int(_) => {
+                f32::from_attribute_value(value).map(Val::Px)
+            }
+            AttributeValue::Text(text) => parse_val(&text),
+            value => downcast_any(&value).ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+/// A single length or [`Val`] sets every side, and text is parsed with [`parse_ui_rect`].
+impl FromAttributeValue for UiRect {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        if let AttributeValue::Text(text) = &value {
+            return parse_ui_rect(text);
+        }
+        if let Some(rect) = downcast_any(&value) {
+            return Ok(rect);
+        }
+        Val::from_attribute_value(value)
+            .map(UiRect::all)
+            .map_err(|_| type_mismatch::<Self>())
+    }
+}
+
+/// Any of Bevy's color spaces converts into a [`Color`], and text is parsed with [`parse_color`].
+impl FromAttributeValue for Color {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        if let AttributeValue::Text(text) = &value {
+            return parse_color(text);
+        }
+        downcast_any::<Color>(&value)
+            .or_else(|| downcast_any::<Srgba>(&value).map(Color::from))
+            .or_else(|| downcast_any::<LinearRgba>(&value).map(Color::from))
+            .or_else(|| downcast_any::<Hsla>(&value).map(Color::from))
+            .or_else(|| downcast_any::<Oklcha>(&value).map(Color::from))
+            .ok_or_else(type_mismatch::<Self>)
+    }
+}
+
+/// A single length or [`Val`] rounds every corner, and text is parsed with
+/// [`parse_border_radius`].
+impl FromAttributeValue for BorderRadius {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        if let AttributeValue::Text(text) = &value {
+            return parse_border_radius(text);
+        }
+        if let Some(radius) = downcast_any(&value) {
+            return Ok(radius);
+        }
+        Val::from_attribute_value(value)
+            .map(BorderRadius::all)
+            .map_err(|_| type_mismatch::<Self>())
+    }
+}
+
+/// An [`OverflowAxis`] applies to both axes, and text is parsed with [`parse_overflow`].
+impl FromAttributeValue for Overflow {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Text(text) => parse_overflow(&text),
+            value => downcast_any(&value)
+                .or_else(|| downcast_any(&value).map(|both| Overflow { x: both, y: both }))
+                .ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+/// A single [`GridTrack`] is a list of one, and text is parsed with [`parse_grid_tracks`].
+impl FromAttributeValue for Vec<GridTrack> {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Text(text) => parse_grid_tracks(&text),
+            value => downcast_any(&value)
+                .or_else(|| downcast_any::<GridTrack>(&value).map(|track| vec![track]))
+                .ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+/// A single [`GridTrack`] or [`RepeatedGridTrack`] is a list of one, and text is parsed with
+/// [`parse_repeated_grid_tracks`].
+impl FromAttributeValue for Vec<RepeatedGridTrack> {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Text(text) => parse_repeated_grid_tracks(&text),
+            value => downcast_any(&value)
+                .or_else(|| downcast_any::<RepeatedGridTrack>(&value).map(|track| vec![track]))
+                .or_else(|| downcast_any::<GridTrack>(&value).map(|track| vec![track.into()]))
+                .ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+/// A number is the start line, and text is parsed with [`parse_grid_placement`].
+impl FromAttributeValue for GridPlacement {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Text(text) => parse_grid_placement(&text),
+            AttributeValue::Int(line) => parse_grid_placement(&line.to_string()),
+            value => downcast_any(&value).ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+/// A single [`ShadowStyle`] is one shadow, and text is parsed with [`parse_box_shadow`].
+impl FromAttributeValue for BoxShadow {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Text(text) => parse_box_shadow(&text),
+            value => downcast_any(&value)
+                .or_else(|| downcast_any(&value).map(|shadow| BoxShadow(vec![shadow])))
+                .ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+/// Numbers are multiples of the font size, like in CSS, and text is parsed with
+/// [`parse_line_height`].
+impl FromAttributeValue for LineHeight {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Float(_) | AttributeValue::Int(_) => {
+                f32::from_attribute_value(value).map(LineHeight::RelativeToFont)
+            }
+            AttributeValue::Text(text) => parse_line_height(&text),
+            _ => Err(type_mismatch::<Self>()),
+        }
+    }
+}
+
+/// A [`TextureSlicer`] 9-slices the image, and text is parsed with [`parse_image_mode`].
+impl FromAttributeValue for NodeImageMode {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::Text(text) => parse_image_mode(&text),
+            value => downcast_any(&value)
+                .map(NodeImageMode::Sliced)
+                .ok_or_else(type_mismatch::<Self>),
+        }
+    }
+}
+
+/// Handles are passed with [`attr`](crate::component::attr).
+impl<A: Asset> FromAttributeValue for Handle<A> {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        downcast_any(&value).ok_or_else(type_mismatch::<Self>)
+    }
+}
+
+/// A removed attribute, or one set to `None`, converts to `None`.
+impl<T: FromAttributeValue> FromAttributeValue for Option<T> {
+    fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+        match value {
+            AttributeValue::None => Ok(None),
+            value => T::from_attribute_value(value).map(Some),
+        }
+    }
+}
+
+macro_rules! enum_attribute_values {
+    ($($enum:ty),*) => {
+        $(
+            /// Text is the name of a variant, parsed with [`parse_enum`].
+            impl FromAttributeValue for $enum {
+                fn from_attribute_value(value: AttributeValue) -> Result<Self, DioxusErrorKind> {
+                    match value {
+                        AttributeValue::Text(text) => parse_enum(&text),
+                        value => downcast_any(&value).ok_or_else(type_mismatch::<Self>),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+enum_attribute_values!(
+    AlignContent,
+    AlignItems,
+    AlignSelf,
+    BoxSizing,
+    Display,
+    FlexDirection,
+    FlexWrap,
+    GridAutoFlow,
+    JustifyContent,
+    JustifyItems,
+    JustifySelf,
+    JustifyText,
+    LineBreak,
+    OverflowAxis,
+    OverflowClipBox,
+    PositionType,
+    Visibility
+);
+
+// Converts attributes in `elements!` mutators, using `FromAttributeValue` when the attribute type
+// implements it. `(&Convert::<T>::new()).convert(value)` picks `ConvertWithTrait` when its bound
+// holds, and otherwise falls back to downcasting the value of `attr(..)` through auto-ref.
+#[doc(hidden)]
+pub struct Convert<T>(PhantomData<T>);
+
+impl<T> Convert<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for Convert<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+pub trait ConvertWithTrait<T> {
+    fn convert(&self, value: AttributeValue) -> Result<T, DioxusErrorKind>;
+}
+
+impl<T: FromAttributeValue> ConvertWithTrait<T> for Convert<T> {
+    fn convert(&self, value: AttributeValue) -> Result<T, DioxusErrorKind> {
+        T::from_attribute_value(value)
+    }
+}
+
+#[doc(hidden)]
+pub trait ConvertAny<T> {
+    fn convert(&self, value: AttributeValue) -> Result<T, DioxusErrorKind>;
+}
+
+impl<T: AnyValue + Clone> ConvertAny<T> for &Convert<T> {
+    fn convert(&self, value: AttributeValue) -> Result<T, DioxusErrorKind> {
+        downcast_any(&value).ok_or_else(type_mismatch::<T>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Compares by `Debug` output, for the types that aren't `PartialEq`.
+    fn assert_parses<T: std::fmt::Debug>(parsed: Result<T, DioxusErrorKind>, expected: T) {
+        assert_eq!(
+            format!("{parsed:?}"),
+            format!("{:?}", Ok::<_, ()>(expected))
+        );
+    }
+
+    #[test]
+    fn parses_lengths() {
+        assert_eq!(parse_val("auto"), Ok(Val::Auto));
+        assert_eq!(parse_val(" AUTO "), Ok(Val::Auto));
+        assert_eq!(parse_val("10"), Ok(Val::Px(10.0)));
+        assert_eq!(parse_val("10px"), Ok(Val::Px(10.0)));
+        assert_eq!(parse_val("-2.5 px"), Ok(Val::Px(-2.5)));
+        assert_eq!(parse_val("50%"), Ok(Val::Percent(50.0)));
+        assert_eq!(parse_val("10vw"), Ok(Val::Vw(10.0)));
+        assert_eq!(parse_val("10vh"), Ok(Val::Vh(10.0)));
+        assert_eq!(parse_val("10vmin"), Ok(Val::VMin(10.0)));
+        assert_eq!(parse_val("10vmax"), Ok(Val::VMax(10.0)));
+        assert!(parse_val("").is_err());
+        assert!(parse_val("px").is_err());
+        assert!(parse_val("10em").is_err());
+        assert!(parse_val("ten").is_err());
+    }
+
+    #[test]
+    fn parses_one_to_four_sides() {
+        let (one, two, three, four) = (Val::Px(1.0), Val::Px(2.0), Val::Px(3.0), Val::Px(4.0));
+        assert_eq!(parse_ui_rect("1px"), Ok(UiRect::all(one)));
+        assert_eq!(
+            parse_ui_rect("1px 2px"),
+            Ok(UiRect::new(two, two, one, one))
+        );
+        assert_eq!(
+            parse_ui_rect("1px 2px 3px"),
+            Ok(UiRect::new(two, two, one, three))
+        );
+        assert_eq!(
+            parse_ui_rect("1px 2px 3px 4px"),
+            Ok(UiRect::new(four, two, one, three))
+        );
+        assert!(parse_ui_rect("").is_err());
+        assert!(parse_ui_rect("1px 2px 3px 4px 5px").is_err());
+        assert!(parse_ui_rect("1px wide").is_err());
+    }
+
+    #[test]
+    fn parses_one_to_four_radii() {
+        let (one, two, three, four) = (Val::Px(1.0), Val::Px(2.0), Val::Px(3.0), Val::Px(4.0));
+        assert_eq!(parse_border_radius("1px"), Ok(BorderRadius::all(one)));
+        assert_eq!(
+            parse_border_radius("1px 2px"),
+            Ok(BorderRadius::new(one, two, one, two))
+        );
+        assert_eq!(
+            parse_border_radius("1px 2px 3px"),
+            Ok(BorderRadius::new(one, two, three, two))
+        );
+        assert_eq!(
+            parse_border_radius("1px 2px 3px 4px"),
+            Ok(BorderRadius::new(one, two, three, four))
+        );
+        assert!(parse_border_radius("").is_err());
+        assert!(parse_border_radius("1px 2px 3px 4px 5px").is_err());
+        assert!(parse_border_radius("round").is_err());
+    }
+
+    #[test]
+    fn parses_colors() {
+        let red = Color::srgb(1.0, 0.0, 0.0);
+        assert_eq!(parse_color("red"), Ok(red));
+        assert_eq!(parse_color(" Red "), Ok(red));
+        assert_eq!(parse_color("#f00"), Ok(red));
+        assert_eq!(parse_color("#ff0000"), Ok(red));
+        assert_eq!(
+            parse_color("#ff000080"),
+            Ok(Color::srgba_u8(255, 0, 0, 128))
+        );
+        assert_eq!(parse_color("rgb(255, 0, 0)"), Ok(red));
+        assert_eq!(
+            parse_color("rgba(255, 0, 0, 0.5)"),
+            Ok(Color::srgba(1.0, 0.0, 0.0, 0.5))
+        );
+        assert_eq!(parse_color("transparent"), Ok(Color::NONE));
+        assert!(parse_color("").is_err());
+        assert!(parse_color("reddish").is_err());
+        assert_eq!(parse_color("#f008"), Ok(Color::srgba_u8(255, 0, 0, 136)));
+        assert!(parse_color("#ff000").is_err());
+        assert!(parse_color("#gg0000").is_err());
+        assert!(parse_color("rgb(255, 0)").is_err());
+        assert!(parse_color("rgb(255, 0, 0").is_err());
+        assert!(parse_color("hsl(0, 100%, 50%)").is_err());
+    }
+
+    #[test]
+    fn parses_enum_variants_ignoring_case_and_separators() {
+        assert_eq!(
+            parse_enum::<JustifyContent>("space-between"),
+            Ok(JustifyContent::SpaceBetween)
+        );
+        assert_eq!(
+            parse_enum::<JustifyContent>("SPACE_BETWEEN"),
+            Ok(JustifyContent::SpaceBetween)
+        );
+        assert!(parse_enum::<JustifyContent>("space").is_err());
+        assert_eq!(
+            parse_overflow("hidden"),
+            Ok(Overflow {
+                x: OverflowAxis::Hidden,
+                y: OverflowAxis::Hidden,
+            })
+        );
+        assert_eq!(
+            parse_overflow("visible scroll"),
+            Ok(Overflow {
+                x: OverflowAxis::Visible,
+                y: OverflowAxis::Scroll,
+            })
+        );
+        assert!(parse_overflow("hidden hidden hidden").is_err());
+    }
+
+    #[test]
+    fn parses_grid_tracks() {
+        assert_eq!(parse_grid_track("100px"), Ok(GridTrack::px(100.0)));
+        assert_eq!(parse_grid_track("auto"), Ok(GridTrack::auto()));
+        assert_eq!(
+            parse_grid_track("min-content"),
+            Ok(GridTrack::min_content())
+        );
+        assert_eq!(parse_grid_track("1fr"), Ok(GridTrack::fr(1.0)));
+        assert_eq!(
+            parse_grid_track("fit-content(100px)"),
+            Ok(GridTrack::fit_content_px(100.0))
+        );
+        assert_eq!(
+            parse_grid_track("minmax(100px, 1fr)"),
+            Ok(GridTrack::minmax(
+                MinTrackSizingFunction::Px(100.0),
+                MaxTrackSizingFunction::Fraction(1.0),
+            ))
+        );
+        assert_eq!(
+            parse_grid_tracks("100px 1fr auto"),
+            Ok(vec![
+                GridTrack::px(100.0),
+                GridTrack::fr(1.0),
+                GridTrack::auto()
+            ])
+        );
+        assert!(parse_grid_track("").is_err());
+        assert!(parse_grid_track("1fx").is_err());
+        assert!(parse_grid_track("minmax(100px)").is_err());
+        assert!(parse_grid_track("fit-content(auto)").is_err());
+        // A fraction can't be the minimum of a track
+        assert!(parse_grid_track("minmax(1fr, 100px)").is_err());
+        assert!(parse_grid_tracks("100px wide").is_err());
+    }
+
+    #[test]
+    fn parses_repeated_grid_tracks() {
+        assert_eq!(
+            parse_repeated_grid_tracks("200px repeat(3, 1fr)"),
+            Ok(vec![
+                RepeatedGridTrack::px(1, 200.0),
+                RepeatedGridTrack::fr(3, 1.0),
+            ])
+        );
+        assert_eq!(
+            parse_repeated_grid_tracks("repeat(auto-fill, minmax(100px, 1fr) auto)"),
+            Ok(vec![RepeatedGridTrack::repeat_many(
+                GridTrackRepetition::AutoFill,
+                vec![
+                    GridTrack::minmax(
+                        MinTrackSizingFunction::Px(100.0),
+                        MaxTrackSizingFunction::Fraction(1.0),
+                    ),
+                    GridTrack::auto(),
+                ],
+            )])
+        );
+        assert!(parse_repeated_grid_tracks("repeat(3)").is_err());
+        assert!(parse_repeated_grid_tracks("repeat(many, 1fr)").is_err());
+        assert!(parse_repeated_grid_tracks("repeat(3, wide)").is_err());
+    }
+
+    #[test]
+    fn parses_grid_placements() {
+        assert_eq!(parse_grid_placement("auto"), Ok(GridPlacement::auto()));
+        assert_eq!(parse_grid_placement("2"), Ok(GridPlacement::start(2)));
+        assert_eq!(parse_grid_placement("-1"), Ok(GridPlacement::start(-1)));
+        assert_eq!(parse_grid_placement("span 2"), Ok(GridPlacement::span(2)));
+        // The span is ignored once both lines are set
+        let placement = parse_grid_placement("1 / 3").unwrap();
+        assert_eq!(
+            (placement.get_start(), placement.get_end()),
+            (Some(1), Some(3))
+        );
+        assert_eq!(
+            parse_grid_placement("2 / span 3"),
+            Ok(GridPlacement::start_span(2, 3))
+        );
+        assert_eq!(parse_grid_placement("auto / 3"), Ok(GridPlacement::end(3)));
+        assert!(parse_grid_placement("").is_err());
+        assert!(parse_grid_placement("0").is_err());
+        assert!(parse_grid_placement("span 0").is_err());
+        assert!(parse_grid_placement("span 2 / span 3").is_err());
+        assert!(parse_grid_placement("1 / 2 / 3").is_err());
+        assert!(parse_grid_placement("1 /").is_err());
+        assert!(parse_grid_placement("first").is_err());
+    }
+
+    #[test]
+    fn parses_box_shadows() {
+        assert_eq!(parse_box_shadow("none"), Ok(BoxShadow::default()));
+        assert_eq!(
+            parse_box_shadow("2px 4px"),
+            Ok(BoxShadow(vec![ShadowStyle {
+                color: Color::BLACK,
+                x_offset: Val::Px(2.0),
+                y_offset: Val::Px(4.0),
+                blur_radius: Val::ZERO,
+                spread_radius: Val::ZERO,
+            }]))
+        );
+        assert_eq!(
+            parse_box_shadow("2px 4px 8px 1px rgba(255, 0, 0, 0.5), red 1px 1px"),
+            Ok(BoxShadow(vec![
+                ShadowStyle {
+                    color: Color::srgba(1.0, 0.0, 0.0, 0.5),
+                    x_offset: Val::Px(2.0),
+                    y_offset: Val::Px(4.0),
+                    blur_radius: Val::Px(8.0),
+                    spread_radius: Val::Px(1.0),
+                },
+                ShadowStyle {
+                    color: Color::srgb(1.0, 0.0, 0.0),
+                    x_offset: Val::Px(1.0),
+                    y_offset: Val::Px(1.0),
+                    blur_radius: Val::ZERO,
+                    spread_radius: Val::ZERO,
+                },
+            ]))
+        );
+        assert!(parse_box_shadow("").is_err());
+        assert!(parse_box_shadow(" , ").is_err());
+        assert!(parse_box_shadow("2px").is_err());
+        assert!(parse_box_shadow("1px 2px 3px 4px 5px").is_err());
+        assert!(parse_box_shadow("1px 2px red blue").is_err());
+        assert!(parse_box_shadow("1px 2px reddish").is_err());
+    }
+
+    #[test]
+    fn parses_line_heights() {
+        assert_parses(parse_line_height("normal"), LineHeight::default());
+        assert_parses(parse_line_height("24px"), LineHeight::Px(24.0));
+        assert_parses(parse_line_height("1.5"), LineHeight::RelativeToFont(1.5));
+        assert_parses(parse_line_height("150%"), LineHeight::RelativeToFont(1.5));
+        assert!(parse_line_height("").is_err());
+        assert!(parse_line_height("tall").is_err());
+        assert!(parse_line_height("2em").is_err());
+    }
+
+    #[test]
+    fn parses_image_modes() {
+        assert_parses(parse_image_mode("auto"), NodeImageMode::Auto);
+        assert_parses(parse_image_mode("Stretch"), NodeImageMode::Stretch);
+        assert_parses(
+            parse_image_mode("tiled-x 2"),
+            NodeImageMode::Tiled {
+                tile_x: true,
+                tile_y: false,
+                stretch_value: 2.0,
+            },
+        );
+        assert_parses(
+            parse_image_mode("sliced 1px 2"),
+            NodeImageMode::Sliced(TextureSlicer {
+                border: BorderRect::axes(2.0, 1.0),
+                ..default()
+            }),
+        );
+        assert_parses(
+            parse_image_mode("sliced 1 2 3 4"),
+            NodeImageMode::Sliced(TextureSlicer {
+                border: BorderRect {
+                    left: 4.0,
+                    right: 2.0,
+                    top: 1.0,
+                    bottom: 3.0,
+                },
+                ..default()
+            }),
+        );
+        assert!(parse_image_mode("").is_err());
+        assert!(parse_image_mode("stretch 2").is_err());
+        assert!(parse_image_mode("tiled 1 2").is_err());
+        assert!(parse_image_mode("sliced").is_err());
+        assert!(parse_image_mode("sliced 1 2 3 4 5").is_err());
+        assert!(parse_image_mode("sliced wide").is_err());
+        assert!(parse_image_mode("cover").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! [`VirtualList`], a list that only renders the rows in view, for lists of thousands of entries
+//! like an inventory or a server browser.
+//!
+//! The list is a library `scroll` element holding a node as tall as all of its rows. Only the rows
+//! that intersect the viewport, plus a few on each side, are rendered inside it, each keyed by its
+//! index, so scrolling keeps the entities of the rows that stay in view, and only spawns the ones
+//! that come into view.
+
+use std::ops::Range;
+
+use dioxus::prelude::*;
+
+use crate::library;
+
+crate::dioxus_elements! {
+    elements: { library }
+}
+
+/// A vertical list of `len` rows of the same height, rendering only those in view.
+///
+/// Every row is as tall as `row_height`. Without it, the first row is rendered alone and measured
+/// once it lays out, and its height is used for all of them, even if it changes later. The list
+/// fills its parent by default, and accepts a `class` and a `style` like any element, to size it
+/// otherwise.
+///
+/// ```rust,ignore
+/// rsx! {
+///     VirtualList { len: items.len(), row_height: 24.0, style: "height: 400px",
+///         row: move |index: usize| rsx! { text { "{items.read()[index].name}" } },
+///     }
+/// }
+/// ```
+#[component]
+pub fn VirtualList(
+    /// The number of rows.
+    len: usize,
+    /// Renders the row at an index.
+    row: Callback<usize, Element>,
+    /// The height of every row in logical pixels, measured from the first row if unset.
+    row_height: Option<f32>,
+    /// How many rows to render past each edge of the viewport, so they are already there when
+    /// scrolled into view.
+    #[props(default = 4)]
+    overscan: usize,
+    class: Option<String>,
+    style: Option<String>,
+) -> Element {
+    let mut offset = use_signal(|| 0.0_f32);
+    let mut viewport = use_signal(|| 0.0_f32);
+    let mut measured = use_signal(|| None::<f32>);
+    let height = use_memo(use_reactive!(
+        |row_height| row_height.or_else(|| *measured.read())
+    ));
+
+    // Only depends on the rows in view, so scrolling within a row doesn't render the list again.
+    let rows = use_memo(use_reactive!(|len, overscan| {
+        rows_in_view(len, height(), offset(), viewport(), overscan)
+    }));
+
+    let height = height().unwrap_or_default();
+    rsx! {
+        scroll {
+            width: "100%",
+            height: "100%",
+            class,
+            style,
+            onscroll: move |event| offset.set(event.data().offset_y),
+            onresize: move |event| viewport.set(event.data().height),
+            node { width: "100%", height: height * len as f32,
+                for index in rows() {
+                    node {
+                        key: "{index}",
+                        position_type: "absolute",
+                        top: height * index as f32,
+                        width: "100%",
+                        height: row_height,
+                        onresize: move |event| {
+                            // Rows that are empty or not laid out yet don't tell the height of the others.
+                            let height = event.data().height;
+                            if height > 0.0 && measured().is_none() {
+                                measured.set(Some(height));
+                            }
+                        },
+                        {row(index)}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The rows of a list of `len` rows as tall as `height` that intersect a viewport as tall as
+/// `viewport`, scrolled down by `offset`, along with `overscan` rows past each of its edges. Until
+/// the height is known, only the first row is, to be measured.
+fn rows_in_view(
+    len: usize,
+    height: Option<f32>,
+    offset: f32,
+    viewport: f32,
+    overscan: usize,
+) -> Range<usize> {
+    let Some(height) = height.filter(|height| *height > 0.0) else {
+        return 0..len.min(1);
+    };
+    let first = (offset / height).floor() as usize;
+    let last = ((offset + viewport) / height).ceil() as usize;
+    first.saturating_sub(overscan).min(len)..last.saturating_add(overscan).min(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rows_in_view;
+
+    #[test]
+    fn renders_the_rows_in_view_and_the_overscan() {
+        assert_eq!(rows_in_view(1000, Some(20.0), 0.0, 100.0, 4), 0..9);
+        assert_eq!(rows_in_view(1000, Some(20.0), 200.0, 100.0, 4), 6..19);
+        // A row partly in view is rendered
+        assert_eq!(rows_in_view(1000, Some(20.0), 210.0, 100.0, 0), 10..16);
+    }
+
+    #[test]
+    fn stays_within_the_list() {
+        assert_eq!(rows_in_view(10, Some(20.0), 180.0, 100.0, 4), 5..10);
+        assert_eq!(rows_in_view(10, Some(20.0), 1000.0, 100.0, 4), 10..10);
+        assert_eq!(rows_in_view(0, Some(20.0), 0.0, 100.0, 4), 0..0);
+    }
+
+    #[test]
+    fn renders_the_first_row_alone_until_it_is_measured() {
+        assert_eq!(rows_in_view(1000, None, 0.0, 100.0, 4), 0..1);
+        assert_eq!(rows_in_view(1000, Some(0.0), 0.0, 100.0, 4), 0..1);
+        assert_eq!(rows_in_view(0, None, 0.0, 100.0, 4), 0..0);
+    }
+}
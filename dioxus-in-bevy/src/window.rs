@@ -0,0 +1,255 @@
+//! Listeners for events that aren't targeted at any element: `onwindowresize`, `onwindowfocus`,
+//! `onfiledrop`, and [`use_window_event`](crate::hooks::use_window_event) for any other Bevy event,
+//! such as [`AppExit`].
+
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
+
+use bevy::ecs::{event::EventCursor, system::SystemState};
+use bevy::prelude::*;
+use bevy::window::{FileDragAndDrop, WindowFocused, WindowResized};
+use dioxus_core::prelude::Callback;
+
+use crate::native::dispatch_event;
+
+pub(crate) fn setup_plugin(app: &mut App) {
+    // Headless apps don't add `WindowPlugin`, but elements may still listen to these.
+    app.add_event::<WindowResized>()
+        .add_event::<WindowFocused>()
+        .add_event::<FileDragAndDrop>()
+        .init_non_send_resource::<HookListeners>()
+        .add_systems(
+            Update,
+            start_hook_cursors
+                .after(crate::native::setup)
+                .after(crate::native::render),
+        )
+        .add_systems(Last, (dispatch_window_events, dispatch_hook_events));
+}
+
+/// Marks an element listening to any of the window events.
+#[derive(Component, Default)]
+pub(crate) struct WindowListener {
+    resize: bool,
+    focus: bool,
+    file_drop: bool,
+}
+
+impl WindowListener {
+    fn flag(&mut self, name: &str) -> Option<&mut bool> {
+        match name {
+            "windowresize" => Some(&mut self.resize),
+            "windowfocus" => Some(&mut self.focus),
+            "filedrop" => Some(&mut self.file_drop),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn add_listener(world: &mut World, entity: Entity, name: &str) {
+    let mut entity = world.entity_mut(entity);
+    if !entity.contains::<WindowListener>() {
+        entity.insert(WindowListener::default());
+    }
+
+    let mut listener = entity.get_mut::<WindowListener>().unwrap();
+    if let Some(flag) = listener.flag(name) {
+        *flag = true;
+    }
+}
+
+pub(crate) fn remove_listener(world: &mut World, entity: Entity, name: &str) {
+    let Ok(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+    let Some(mut listener) = entity.get_mut::<WindowListener>() else {
+        return;
+    };
+
+    if let Some(flag) = listener.flag(name) {
+        *flag = false;
+    }
+    if !listener.resize && !listener.focus && !listener.file_drop {
+        entity.remove::<WindowListener>();
+    }
+}
+
+fn dispatch_window_events(
+    world: &mut World,
+    readers: &mut SystemState<(
+        EventReader<WindowResized>,
+        EventReader<WindowFocused>,
+        EventReader<FileDragAndDrop>,
+    )>,
+) {
+    let (mut resized, mut focused, mut dropped) = readers.get_mut(world);
+    let resized = resized.read().cloned().collect::<Vec<_>>();
+    let focused = focused.read().cloned().collect::<Vec<_>>();
+    let dropped = dropped.read().cloned().collect::<Vec<_>>();
+
+    if resized.is_empty() && focused.is_empty() && dropped.is_empty() {
+        return;
+    }
+
+    let listeners = world
+        .query::<(Entity, &WindowListener)>()
+        .iter(world)
+        .map(|(entity, listener)| (entity, listener.resize, listener.focus, listener.file_drop))
+        .collect::<Vec<_>>();
+
+    for (entity, resize, focus, file_drop) in listeners {
+        if resize {
+            for event in &resized {
+                dispatch_event(world, entity, "windowresize", event.clone(), false);
+            }
+        }
+        if focus {
+            for event in &focused {
+                dispatch_event(world, entity, "windowfocus", event.clone(), false);
+            }
+        }
+        if file_drop {
+            for event in &dropped {
+                dispatch_event(world, entity, "filedrop", event.clone(), false);
+            }
+        }
+    }
+}
+
+/// The callbacks registered with [`use_window_event`](crate::hooks::use_window_event) by the
+/// components of a world, by event type.
+///
+/// It is a `NonSend` resource of the world, and is provided to the `VirtualDom` of each of its roots
+/// too, so hooks listen to the events of the app rendering them.
+#[derive(Clone, Default)]
+pub(crate) struct HookListeners(Rc<RefCell<HookListenerMap>>);
+
+#[derive(Default)]
+struct HookListenerMap {
+    next_id: usize,
+    events: HashMap<TypeId, EventHookListeners>,
+}
+
+/// The callbacks registered for one event type.
+struct EventHookListeners {
+    start: fn(&mut World),
+    read: fn(&mut World),
+    callbacks: Vec<(usize, Box<dyn Any>)>,
+}
+
+impl HookListeners {
+    pub(crate) fn add<E: Event + Clone>(&self, callback: Callback<E>) -> usize {
+        let mut listeners = self.0.borrow_mut();
+        let id = listeners.next_id;
+        listeners.next_id += 1;
+
+        listeners
+            .events
+            .entry(TypeId::of::<E>())
+            .or_insert_with(|| EventHookListeners {
+                start: start_hook_events::<E>,
+                read: read_hook_events::<E>,
+                callbacks: Vec::new(),
+            })
+            .callbacks
+            .push((id, Box::new(callback)));
+
+        id
+    }
+
+    pub(crate) fn remove<E: Event>(&self, id: usize) {
+        if let Some(listeners) = self.0.borrow_mut().events.get_mut(&TypeId::of::<E>()) {
+            listeners.callbacks.retain(|(listener, _)| *listener != id);
+        }
+    }
+
+    fn callbacks<E: Event>(&self) -> Vec<Callback<E>> {
+        self.0
+            .borrow()
+            .events
+            .get(&TypeId::of::<E>())
+            .map(|listeners| {
+                listeners
+                    .callbacks
+                    .iter()
+                    .filter_map(|(_, callback)| callback.downcast_ref::<Callback<E>>().copied())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Where `use_window_event` listeners for `E` are up to in its event queue.
+#[derive(Resource)]
+struct HookEventCursor<E: Event>(EventCursor<E>);
+
+/// Starts reading the events listened to by the components that just rendered, from the events
+/// sent after they did, so the listeners get every event of the frame from there.
+fn start_hook_cursors(world: &mut World) {
+    let starts = world
+        .non_send_resource::<HookListeners>()
+        .0
+        .borrow()
+        .events
+        .values()
+        .map(|listeners| listeners.start)
+        .collect::<Vec<_>>();
+
+    for start in starts {
+        start(world);
+    }
+}
+
+fn start_hook_events<E: Event>(world: &mut World) {
+    if world.contains_resource::<HookEventCursor<E>>() {
+        return;
+    }
+    if let Some(events) = world.get_resource::<Events<E>>() {
+        let cursor = events.get_cursor_current();
+        world.insert_resource(HookEventCursor(cursor));
+    }
+}
+
+fn dispatch_hook_events(world: &mut World) {
+    // Keep reading events without listeners too, so one added later doesn't get stale events.
+    let readers = world
+        .non_send_resource::<HookListeners>()
+        .0
+        .borrow()
+        .events
+        .values()
+        .map(|listeners| listeners.read)
+        .collect::<Vec<_>>();
+
+    for read in readers {
+        read(world);
+    }
+}
+
+fn read_hook_events<E: Event + Clone>(world: &mut World) {
+    if !world.contains_resource::<HookEventCursor<E>>() {
+        start_hook_events::<E>(world);
+        return;
+    }
+
+    let events = world.resource_scope(|world, mut cursor: Mut<HookEventCursor<E>>| {
+        world
+            .get_resource::<Events<E>>()
+            .map(|events| cursor.0.read(events).cloned().collect::<Vec<_>>())
+            .unwrap_or_default()
+    });
+    if events.is_empty() {
+        return;
+    }
+
+    let callbacks = world.non_send_resource::<HookListeners>().callbacks::<E>();
+    for event in events {
+        for callback in &callbacks {
+            callback.call(event.clone());
+        }
+    }
+}